@@ -0,0 +1,49 @@
+// SPDX-FileCopyrightText: 2026 Antoni Szymański
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::parse::{Parser, READ_LIMIT, trim_line_ending};
+use crate::{FromReaderError, GitCredential, ParseOptions, ReadLineCtx, ToWriterError, WriteCtx};
+use snafu::ResultExt;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use zeroize::{Zeroize, Zeroizing};
+
+impl GitCredential {
+    /// Like [`GitCredential::from_reader`], but for an asynchronous reader.
+    pub async fn from_async_reader(reader: impl AsyncRead + Unpin) -> Result<Self, FromReaderError> {
+        ParseOptions::lenient().parse_async(reader).await
+    }
+
+    /// Like [`GitCredential::to_writer`], but for an asynchronous writer.
+    pub async fn to_async_writer(&self, mut writer: impl AsyncWrite + Unpin) -> Result<(), ToWriterError> {
+        let mut buf = Vec::new();
+        self.to_writer(&mut buf)?;
+        let result = writer.write_all(&buf).await.context(WriteCtx);
+        buf.zeroize();
+        result
+    }
+}
+
+impl ParseOptions {
+    /// Like [`ParseOptions::parse`], but for an asynchronous reader.
+    pub async fn parse_async(&self, reader: impl AsyncRead + Unpin) -> Result<GitCredential, FromReaderError> {
+        let mut reader = BufReader::new(reader);
+        let mut parser = Parser::new(*self);
+        let mut buf = Zeroizing::new(Vec::new());
+        loop {
+            buf.clear();
+            let line_number = parser.next_line_number();
+            let n = (&mut reader)
+                .take(READ_LIMIT)
+                .read_until(b'\n', &mut buf)
+                .await
+                .context(ReadLineCtx { line_number })?;
+            if n == 0 {
+                return parser.finish(false);
+            }
+            trim_line_ending(&mut buf);
+            if !parser.feed(&buf)? {
+                return parser.finish(true);
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+// SPDX-FileCopyrightText: 2026 Antoni Szymański
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::{Capabilities, GitCredential, Secret};
+use std::time::SystemTime;
+
+/// A builder for [`GitCredential`]. See [`GitCredential::builder`].
+#[derive(Debug, Default, Clone)]
+pub struct GitCredentialBuilder {
+    credential: GitCredential,
+}
+
+impl GitCredential {
+    pub fn builder() -> GitCredentialBuilder {
+        GitCredentialBuilder::default()
+    }
+}
+
+impl GitCredentialBuilder {
+    pub fn protocol(mut self, protocol: impl Into<String>) -> Self {
+        self.credential.protocol = Some(protocol.into());
+        self
+    }
+
+    pub fn host(mut self, host: impl Into<String>) -> Self {
+        self.credential.host = Some(host.into());
+        self
+    }
+
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.credential.path = Some(path.into());
+        self
+    }
+
+    pub fn username(mut self, username: impl Into<String>) -> Self {
+        self.credential.username = Some(username.into());
+        self
+    }
+
+    pub fn password(mut self, password: impl Into<Secret>) -> Self {
+        self.credential.password = Some(password.into());
+        self
+    }
+
+    pub fn password_expiry_utc(mut self, password_expiry_utc: SystemTime) -> Self {
+        self.credential.password_expiry_utc = Some(password_expiry_utc);
+        self
+    }
+
+    pub fn oauth_refresh_token(mut self, oauth_refresh_token: impl Into<Secret>) -> Self {
+        self.credential.oauth_refresh_token = Some(oauth_refresh_token.into());
+        self
+    }
+
+    pub fn authtype(mut self, authtype: impl Into<String>) -> Self {
+        self.credential.authtype = Some(authtype.into());
+        self
+    }
+
+    pub fn credential(mut self, credential: impl Into<Secret>) -> Self {
+        self.credential.credential = Some(credential.into());
+        self
+    }
+
+    pub fn ephemeral(mut self, ephemeral: bool) -> Self {
+        self.credential.ephemeral = ephemeral;
+        self
+    }
+
+    pub fn capabilities(mut self, capabilities: Capabilities) -> Self {
+        self.credential.capabilities = capabilities;
+        self
+    }
+
+    /// Appends a `WWW-Authenticate` header.
+    pub fn wwwauth(mut self, wwwauth: impl Into<String>) -> Self {
+        self.credential.wwwauth.push(wwwauth.into());
+        self
+    }
+
+    /// Appends a `state[]` value.
+    pub fn state(mut self, state: impl Into<String>) -> Self {
+        self.credential.state.push(state.into());
+        self
+    }
+
+    pub fn quit(mut self, quit: bool) -> Self {
+        self.credential.quit = quit;
+        self
+    }
+
+    /// Appends an attribute not known to this crate.
+    pub fn extra(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.credential.extra.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn build(self) -> GitCredential {
+        self.credential
+    }
+}
@@ -0,0 +1,605 @@
+// SPDX-FileCopyrightText: 2026 Antoni Szymański
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::matching::HELPER_MATCH;
+use crate::{Capabilities, FromReaderError, GitCredential, Helper, ToWriterError};
+use snafu::{OptionExt, ResultExt, Snafu, ensure};
+use std::ffi::{OsStr, OsString};
+use std::fs::{self, DirBuilder};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::Shutdown;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+use zeroize::Zeroizing;
+
+/// How long a daemon waits for its first credential before exiting, like `git credential-cache--daemon`.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// An in-memory credential cache served over a Unix socket, compatible with `git credential-cache--daemon`.
+#[derive(Debug)]
+pub struct CacheDaemon {
+    listener: UnixListener,
+    socket: PathBuf,
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    credential: GitCredential,
+    expiration: Instant,
+}
+
+/// A client for a [`CacheDaemon`] or `git credential-cache--daemon`. See [`run_cache_client`] for a helper program
+/// that behaves like `git credential-cache`.
+#[derive(Debug, Clone)]
+pub struct CacheClient {
+    socket: PathBuf,
+    timeout: Duration,
+    daemon_command: Option<Vec<OsString>>,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(context(suffix(Ctx)))]
+#[non_exhaustive]
+pub enum CacheError {
+    #[snafu(display("Socket directory {} is accessible by other users", path.display()))]
+    InsecureDirectory { path: PathBuf },
+    #[snafu(display("Failed to create socket directory {}", path.display()))]
+    CreateDirectory { source: io::Error, path: PathBuf },
+    #[snafu(display("Failed to listen on socket {}", path.display()))]
+    Bind { source: io::Error, path: PathBuf },
+    #[snafu(display("Failed to connect to socket {}", path.display()))]
+    Connect { source: io::Error, path: PathBuf },
+    #[snafu(display("No cache daemon is listening on socket {}", path.display()))]
+    NotRunning { path: PathBuf },
+    #[snafu(display("Failed to start cache daemon"))]
+    SpawnDaemon { source: io::Error },
+    #[snafu(display("Cache daemon did not report that it started"))]
+    DaemonNotReady,
+    #[snafu(display("Failed to send request to cache daemon"))]
+    SendRequest { source: io::Error },
+    #[snafu(display("Failed to write credential to cache daemon"))]
+    WriteCredential { source: ToWriterError },
+    #[snafu(display("Failed to read credential from cache daemon"))]
+    ReadCredential { source: FromReaderError },
+    #[snafu(display("Unexpected argument {argument:?}"))]
+    InvalidArgument { argument: String },
+    #[snafu(display("Option {option} requires a value"))]
+    MissingValue { option: &'static str },
+    #[snafu(display("Invalid timeout {value:?}"))]
+    InvalidTimeout { value: String },
+    #[snafu(display("No action was given"))]
+    MissingAction,
+    #[snafu(display("Failed to find the default socket path"))]
+    NoSocketPath,
+    #[snafu(display("Failed to find the current executable"))]
+    CurrentExe { source: io::Error },
+    #[snafu(display("Failed to read credential from input"))]
+    ReadInput { source: FromReaderError },
+    #[snafu(display("Failed to write credential to output"))]
+    WriteOutput { source: ToWriterError },
+}
+
+impl CacheDaemon {
+    /// The socket `git credential-cache` uses by default: `~/.git-credential-cache/socket` if that directory exists,
+    /// otherwise `$XDG_CACHE_HOME/git/credential/socket` (or `~/.cache/git/credential/socket`).
+    pub fn default_socket_path() -> Option<PathBuf> {
+        let home = std::env::home_dir();
+        if let Some(old_dir) = home.as_ref().map(|home| home.join(".git-credential-cache"))
+            && old_dir.is_dir()
+        {
+            return Some(old_dir.join("socket"));
+        }
+        let xdg_cache_home = std::env::var_os("XDG_CACHE_HOME")
+            .filter(|s| !s.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| home.map(|home| home.join(".cache")))?;
+        Some(xdg_cache_home.join("git").join("credential").join("socket"))
+    }
+
+    /// Listens on `socket`, replacing any stale socket file.
+    ///
+    /// Like git, this creates the socket directory with owner-only permissions, and refuses to use an existing one
+    /// that other users can access.
+    pub fn bind(socket: impl Into<PathBuf>) -> Result<Self, CacheError> {
+        let socket = socket.into();
+        if let Some(dir) = socket.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            match fs::metadata(dir) {
+                Ok(metadata) => {
+                    ensure!(
+                        metadata.permissions().mode() & 0o077 == 0,
+                        InsecureDirectoryCtx { path: dir }
+                    );
+                }
+                Err(_) => {
+                    let mut builder = DirBuilder::new();
+                    builder.recursive(true).mode(0o700);
+                    builder.create(dir).context(CreateDirectoryCtx { path: dir })?;
+                }
+            }
+        }
+        match fs::remove_file(&socket) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err).context(BindCtx { path: socket }),
+            _ => {}
+        }
+        let listener = UnixListener::bind(&socket).context(BindCtx { path: &socket })?;
+        Ok(Self {
+            listener,
+            socket,
+            entries: Vec::new(),
+        })
+    }
+
+    pub fn socket(&self) -> &Path {
+        &self.socket
+    }
+
+    /// Serves clients until a client sends `exit`, or until every cached credential has expired. Removes the socket
+    /// before returning.
+    pub fn run(mut self) {
+        let (tx, rx) = mpsc::channel();
+        let listener = self.listener;
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if tx.send(stream).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let idle_until = Instant::now() + IDLE_TIMEOUT;
+        let mut exit_client = None;
+        while let Some(wait) = expire(&mut self.entries, idle_until) {
+            match rx.recv_timeout(wait) {
+                Ok(Ok(stream)) => {
+                    if serve(&mut self.entries, &stream) {
+                        exit_client = Some(stream);
+                        break;
+                    }
+                }
+                Ok(Err(_)) | Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+
+        // Wake up the accepting thread so that it notices the channel is closed.
+        drop(rx);
+        let _ = UnixStream::connect(&self.socket);
+        let _ = fs::remove_file(&self.socket);
+        // Only tell the exiting client that we are done once the socket is gone.
+        drop(exit_client);
+    }
+}
+
+/// Removes expired entries and returns how long to wait for the next client, or `None` if the daemon should exit.
+fn expire(entries: &mut Vec<Entry>, idle_until: Instant) -> Option<Duration> {
+    let now = Instant::now();
+    entries.retain(|entry| entry.expiration > now);
+    let next = match entries.iter().map(|entry| entry.expiration).min() {
+        Some(next) => next,
+        None if idle_until > now => idle_until,
+        None => return None,
+    };
+    Some(next - now)
+}
+
+/// Serves a single client. Returns `true` if the client asked the daemon to exit.
+fn serve(entries: &mut Vec<Entry>, stream: &UnixStream) -> bool {
+    let mut reader = BufReader::new(stream);
+    let Some((action, timeout)) = read_header(&mut reader) else {
+        return false;
+    };
+    let Ok(request) = GitCredential::from_buf_reader(&mut reader) else {
+        return false;
+    };
+    match action.as_str() {
+        "get" => {
+            if let Some(entry) = entries
+                .iter()
+                .find(|entry| entry.credential.matches(&request, HELPER_MATCH))
+            {
+                let entry = &entry.credential;
+                let response = GitCredential {
+                    username: entry.username.clone(),
+                    password: entry.password.clone(),
+                    password_expiry_utc: entry.password_expiry_utc,
+                    oauth_refresh_token: entry.oauth_refresh_token.clone(),
+                    authtype: entry.authtype.clone(),
+                    credential: entry.credential.clone(),
+                    capabilities: request.capabilities & Capabilities::AUTHTYPE,
+                    ..Default::default()
+                };
+                let _ = response.to_writer(stream);
+            }
+        }
+        "exit" => return true,
+        "erase" => entries.retain(|entry| !entry.credential.matches(&request, HELPER_MATCH.match_password(true))),
+        "store" => {
+            // Like git, a pre-encoded credential needs only one of `authtype` and `credential`.
+            let complete = (request.username.is_some() && request.password.is_some())
+                || request.authtype.is_some()
+                || request.credential.is_some();
+            if let Some(timeout) = timeout
+                && complete
+                && !request.ephemeral
+            {
+                entries.retain(|entry| !entry.credential.matches(&request, HELPER_MATCH));
+                entries.push(Entry {
+                    credential: request,
+                    expiration: Instant::now() + timeout,
+                });
+            }
+        }
+        _ => {}
+    }
+    false
+}
+
+/// Reads the `action=` and `timeout=` lines that precede the credential in a request.
+fn read_header(reader: &mut impl BufRead) -> Option<(String, Option<Duration>)> {
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let action = line.trim_end_matches('\n').strip_prefix("action=")?.to_owned();
+    line.clear();
+    reader.read_line(&mut line).ok()?;
+    let timeout = line.trim_end_matches('\n').strip_prefix("timeout=")?;
+    let timeout = timeout.parse::<u64>().ok().map(Duration::from_secs);
+    Some((action, timeout))
+}
+
+/// Runs a [`CacheDaemon`] on `socket` as the `main` of a daemon program.
+///
+/// Prints `ok` to stdout once the daemon is listening, which is what [`CacheClient::daemon_command`] waits for.
+/// Errors are printed to stderr and reported with a failure exit code.
+pub fn run_cache_daemon(socket: impl Into<PathBuf>) -> ExitCode {
+    match CacheDaemon::bind(socket) {
+        Ok(daemon) => {
+            let mut stdout = io::stdout();
+            let _ = writeln!(stdout, "ok").and_then(|()| stdout.flush());
+            daemon.run();
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: {}", snafu::Report::from_error(err));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Runs a client compatible with `git credential-cache` as the `main` of a helper program.
+///
+/// Accepts `[--timeout=<seconds>] [--socket=<path>] <action>` like `git credential-cache`, where the action is `get`,
+/// `store`, `erase` or `exit`. If no daemon is running when a credential is stored, one is started by running the
+/// current executable with `--daemon <socket>`, which this function handles by calling [`run_cache_daemon`].
+/// Errors are printed to stderr and reported with a failure exit code.
+pub fn run_cache_client() -> ExitCode {
+    let args: Vec<OsString> = std::env::args_os().skip(1).collect();
+    if let [option, socket] = args.as_slice()
+        && option == "--daemon"
+    {
+        return run_cache_daemon(socket);
+    }
+    match run_client(&args, io::stdin().lock(), io::stdout().lock()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", snafu::Report::from_error(err));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Runs a single `git credential-cache` invocation with the arguments `args`.
+fn run_client(args: &[OsString], input: impl Read, mut output: impl Write) -> Result<(), CacheError> {
+    let mut timeout = Duration::from_secs(900);
+    let mut socket = None;
+    let mut action = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let bytes = arg.as_bytes();
+        if let Some(value) = bytes.strip_prefix(b"--socket=") {
+            socket = Some(PathBuf::from(OsStr::from_bytes(value)));
+        } else if arg == "--socket" {
+            socket = Some(PathBuf::from(
+                args.next().context(MissingValueCtx { option: "--socket" })?,
+            ));
+        } else if let Some(value) = bytes.strip_prefix(b"--timeout=") {
+            timeout = parse_timeout(OsStr::from_bytes(value))?;
+        } else if arg == "--timeout" {
+            timeout = parse_timeout(args.next().context(MissingValueCtx { option: "--timeout" })?)?;
+        } else if bytes.starts_with(b"-") || action.is_some() {
+            return InvalidArgumentCtx {
+                argument: arg.to_string_lossy(),
+            }
+            .fail();
+        } else {
+            action = Some(arg.to_string_lossy());
+        }
+    }
+    let action = action.context(MissingActionCtx)?;
+    let socket = match socket {
+        Some(socket) => socket,
+        None => CacheDaemon::default_socket_path().context(NoSocketPathCtx)?,
+    };
+    let client = CacheClient::new(&socket).timeout(timeout);
+
+    match &*action {
+        "get" => {
+            let query = GitCredential::from_reader(input).context(ReadInputCtx)?;
+            if let Some(answer) = client.get(&query)? {
+                answer.to_writer(&mut output).context(WriteOutputCtx)?;
+            }
+        }
+        "store" => {
+            let credential = GitCredential::from_reader(input).context(ReadInputCtx)?;
+            let exe = std::env::current_exe().context(CurrentExeCtx)?;
+            let client = client.daemon_command([exe.into_os_string(), "--daemon".into(), socket.into_os_string()]);
+            client.store(&credential)?;
+        }
+        "erase" => {
+            let query = GitCredential::from_reader(input).context(ReadInputCtx)?;
+            client.erase(&query)?;
+        }
+        "exit" => client.exit()?,
+        // Like git, ignore operations added in the future.
+        _ => {}
+    }
+    Ok(())
+}
+
+fn parse_timeout(value: &OsStr) -> Result<Duration, CacheError> {
+    let value = value.to_string_lossy();
+    let seconds = value.parse().ok().context(InvalidTimeoutCtx { value: &*value })?;
+    Ok(Duration::from_secs(seconds))
+}
+
+impl CacheClient {
+    /// Creates a client for the daemon listening on `socket`, which caches credentials for 900 seconds like
+    /// `git credential-cache`.
+    pub fn new(socket: impl Into<PathBuf>) -> Self {
+        Self {
+            socket: socket.into(),
+            timeout: Duration::from_secs(900),
+            daemon_command: None,
+        }
+    }
+
+    /// How long stored credentials are cached.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// The command to start a daemon with if none is running when a credential is stored. It must print `ok` to
+    /// stdout once it is listening, like [`run_cache_daemon`] does.
+    pub fn daemon_command<I, S>(mut self, argv: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<OsString>,
+    {
+        self.daemon_command = Some(argv.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Returns the cached credential matching `query`, or `None` if there is none or no daemon is running.
+    pub fn get(&self, query: &GitCredential) -> Result<Option<GitCredential>, CacheError> {
+        let Some(stream) = self.send("get", Some(query), false)? else {
+            return Ok(None);
+        };
+        let response = GitCredential::from_reader(stream).context(ReadCredentialCtx)?;
+        let found = response.username.is_some() || response.password.is_some() || response.credential.is_some();
+        Ok(found.then_some(response))
+    }
+
+    /// Caches `credential`, starting a daemon with [`CacheClient::daemon_command`] if none is running.
+    pub fn store(&self, credential: &GitCredential) -> Result<(), CacheError> {
+        self.send("store", Some(credential), true).map(drop)
+    }
+
+    /// Removes the cached credentials matching `query`.
+    pub fn erase(&self, query: &GitCredential) -> Result<(), CacheError> {
+        self.send("erase", Some(query), false).map(drop)
+    }
+
+    /// Tells the daemon to exit, and waits until it has removed its socket.
+    pub fn exit(&self) -> Result<(), CacheError> {
+        if let Some(mut stream) = self.send("exit", None, false)? {
+            let _ = io::copy(&mut stream, &mut io::sink());
+        }
+        Ok(())
+    }
+
+    /// Sends a request and returns the stream to read the response from, or `None` if no daemon is running and
+    /// `spawn` is false.
+    fn send(
+        &self,
+        action: &str,
+        credential: Option<&GitCredential>,
+        spawn: bool,
+    ) -> Result<Option<UnixStream>, CacheError> {
+        let stream = match UnixStream::connect(&self.socket) {
+            Ok(stream) => stream,
+            Err(err) if matches!(err.kind(), io::ErrorKind::NotFound | io::ErrorKind::ConnectionRefused) => {
+                if !spawn {
+                    return Ok(None);
+                }
+                self.spawn_daemon()?;
+                UnixStream::connect(&self.socket).context(ConnectCtx { path: &self.socket })?
+            }
+            Err(err) => return Err(err).context(ConnectCtx { path: &self.socket }),
+        };
+        let mut request = Zeroizing::new(format!("action={action}\ntimeout={}\n", self.timeout.as_secs()).into_bytes());
+        if let Some(credential) = credential {
+            credential.to_writer(&mut *request).context(WriteCredentialCtx)?;
+        }
+        (&stream).write_all(&request).context(SendRequestCtx)?;
+        stream.shutdown(Shutdown::Write).context(SendRequestCtx)?;
+        Ok(Some(stream))
+    }
+
+    fn spawn_daemon(&self) -> Result<(), CacheError> {
+        let (program, args) = self
+            .daemon_command
+            .as_deref()
+            .and_then(<[_]>::split_first)
+            .context(NotRunningCtx { path: &self.socket })?;
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .spawn()
+            .context(SpawnDaemonCtx)?;
+        let mut line = String::new();
+        let stdout = child.stdout.take().expect("stdout is piped");
+        BufReader::new(stdout).read_line(&mut line).context(SpawnDaemonCtx)?;
+        ensure!(line == "ok\n", DaemonNotReadyCtx);
+        Ok(())
+    }
+}
+
+impl Helper for CacheClient {
+    type Error = CacheError;
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::AUTHTYPE
+    }
+
+    fn get(&mut self, request: &GitCredential) -> Result<Option<GitCredential>, CacheError> {
+        CacheClient::get(self, request)
+    }
+
+    fn store(&mut self, credential: &GitCredential) -> Result<(), CacheError> {
+        CacheClient::store(self, credential)
+    }
+
+    fn erase(&mut self, credential: &GitCredential) -> Result<(), CacheError> {
+        CacheClient::erase(self, credential)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{expose, request, temp_dir};
+
+    fn start_daemon(socket: &Path) -> thread::JoinHandle<()> {
+        let daemon = CacheDaemon::bind(socket).unwrap();
+        thread::spawn(move || daemon.run())
+    }
+
+    #[test]
+    fn round_trip() {
+        let dir = temp_dir("cache-round-trip");
+        let socket = dir.join("socket");
+        let daemon = start_daemon(&socket);
+        let client = CacheClient::new(&socket);
+
+        let credential = GitCredential::builder()
+            .protocol("https")
+            .host("example.com")
+            .username("user")
+            .password("secret")
+            .oauth_refresh_token("refresh")
+            .build();
+        client.store(&credential).unwrap();
+        let answer = client.get(&request()).unwrap().unwrap();
+        assert_eq!(answer.username.as_deref(), Some("user"));
+        assert_eq!(expose(&answer.password), Some("secret"));
+        assert_eq!(expose(&answer.oauth_refresh_token), Some("refresh"));
+        let other_host = GitCredential::builder().protocol("https").host("example.org").build();
+        assert!(client.get(&other_host).unwrap().is_none());
+
+        client.erase(&credential).unwrap();
+        assert!(client.get(&request()).unwrap().is_none());
+
+        client.exit().unwrap();
+        daemon.join().unwrap();
+        assert!(!socket.exists());
+        // Without a daemon, lookups find nothing and storing needs a daemon command.
+        assert!(client.get(&request()).unwrap().is_none());
+        assert!(matches!(client.store(&credential), Err(CacheError::NotRunning { .. })));
+    }
+
+    #[test]
+    fn stores_pre_encoded_credential() {
+        let dir = temp_dir("cache-authtype");
+        let socket = dir.join("socket");
+        let daemon = start_daemon(&socket);
+        let client = CacheClient::new(&socket);
+
+        let ephemeral = GitCredential::builder()
+            .protocol("https")
+            .host("example.com")
+            .capabilities(Capabilities::AUTHTYPE)
+            .authtype("Bearer")
+            .credential("ephemeral")
+            .ephemeral(true)
+            .build();
+        client.store(&ephemeral).unwrap();
+        let credential = GitCredential::builder()
+            .protocol("https")
+            .host("example.com")
+            .capabilities(Capabilities::AUTHTYPE)
+            .credential("token")
+            .build();
+        client.store(&credential).unwrap();
+
+        let mut query = request();
+        query.capabilities = Capabilities::AUTHTYPE;
+        let answer = client.get(&query).unwrap().unwrap();
+        assert_eq!(expose(&answer.credential), Some("token"));
+
+        client.exit().unwrap();
+        daemon.join().unwrap();
+    }
+
+    #[test]
+    fn client_arguments() {
+        let dir = temp_dir("cache-client");
+        let socket = dir.join("socket");
+        let daemon = start_daemon(&socket);
+        let args = |args: &[&str]| args.iter().map(OsString::from).collect::<Vec<_>>();
+        let socket_arg = format!("--socket={}", socket.display());
+
+        let input = "protocol=https\nhost=example.com\nusername=user\npassword=secret\n";
+        run_client(
+            &args(&["--timeout=60", &socket_arg, "store"]),
+            input.as_bytes(),
+            io::sink(),
+        )
+        .unwrap();
+        let mut output = Vec::new();
+        let socket_str = socket.to_str().unwrap();
+        let get = args(&["--timeout", "60", "--socket", socket_str, "get"]);
+        run_client(&get, "protocol=https\nhost=example.com\n".as_bytes(), &mut output).unwrap();
+        assert_eq!(output, b"username=user\npassword=secret\n");
+
+        let unknown = args(&[&socket_arg, "unknown"]);
+        run_client(&unknown, io::empty(), io::sink()).unwrap();
+        assert!(matches!(
+            run_client(&args(&["--timeout=soon", "get"]), io::empty(), io::sink()),
+            Err(CacheError::InvalidTimeout { .. })
+        ));
+        assert!(matches!(
+            run_client(&args(&["--bogus", "get"]), io::empty(), io::sink()),
+            Err(CacheError::InvalidArgument { .. })
+        ));
+        assert!(matches!(
+            run_client(&args(&["--socket"]), io::empty(), io::sink()),
+            Err(CacheError::MissingValue { .. })
+        ));
+        assert!(matches!(
+            run_client(&args(&[&socket_arg]), io::empty(), io::sink()),
+            Err(CacheError::MissingAction)
+        ));
+
+        run_client(&args(&[&socket_arg, "exit"]), io::empty(), io::sink()).unwrap();
+        daemon.join().unwrap();
+        assert!(!socket.exists());
+    }
+}
@@ -0,0 +1,241 @@
+// SPDX-FileCopyrightText: 2026 Antoni Szymański
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::{Capabilities, GitCredential, HelperInvocation};
+use snafu::Snafu;
+use std::time::SystemTime;
+
+/// An ordered list of credential helpers, consulted the way `git credential fill`, `approve` and `reject` do.
+///
+/// Like git, the chain ignores helpers that fail to run or exit with an error.
+#[derive(Debug, Default, Clone)]
+pub struct CredentialChain {
+    helpers: Vec<HelperInvocation>,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(context(suffix(Ctx)))]
+#[non_exhaustive]
+pub enum FillError {
+    #[snafu(display("Credential helper {command:?} told us to quit"))]
+    Quit { command: String },
+    #[snafu(display("No credential helper provided a complete credential"))]
+    NotFound,
+}
+
+impl CredentialChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a helper the way a `credential.helper` config value does. An empty value removes all previous helpers.
+    pub fn add(&mut self, helper: &str) {
+        if helper.is_empty() {
+            self.helpers.clear();
+        } else {
+            self.helpers.push(HelperInvocation::new(helper));
+        }
+    }
+
+    pub fn helpers(&self) -> &[HelperInvocation] {
+        &self.helpers
+    }
+
+    /// Asks each helper in turn for the missing parts of `credential`, merging their answers into it.
+    ///
+    /// Stops once both a username and a password are known, or an `authtype` and `credential` if the helper supports
+    /// them. Passwords and credentials that have already expired are discarded.
+    pub fn fill(&self, credential: &mut GitCredential) -> Result<(), FillError> {
+        if credential.username.is_some() && credential.password.is_some() {
+            return Ok(());
+        }
+        for helper in &self.helpers {
+            let mut helper_capabilities = Capabilities::empty();
+            if let Ok(answer) = helper.get(credential) {
+                helper_capabilities = answer.capabilities;
+                merge(credential, answer);
+            }
+            if credential.password_expiry_utc.is_some_and(|t| t < SystemTime::now()) {
+                credential.password = None;
+                credential.credential = None;
+                credential.password_expiry_utc = None;
+            }
+            if (credential.capabilities & helper_capabilities).contains(Capabilities::AUTHTYPE)
+                && credential.authtype.is_some()
+                && credential.credential.is_some()
+            {
+                return Ok(());
+            }
+            if credential.username.is_some() && credential.password.is_some() {
+                return Ok(());
+            }
+            if credential.quit {
+                return QuitCtx {
+                    command: helper.command(),
+                }
+                .fail();
+            }
+        }
+        NotFoundCtx.fail()
+    }
+
+    /// Tells each helper to store `credential`, which was accepted by the remote.
+    ///
+    /// Incomplete and expired credentials are not stored.
+    pub fn approve(&self, credential: &GitCredential) {
+        if (credential.username.is_none() || credential.password.is_none()) && credential.credential.is_none() {
+            return;
+        }
+        if credential.password_expiry_utc.is_some_and(|t| t < SystemTime::now()) {
+            return;
+        }
+        for helper in &self.helpers {
+            let _ = helper.store(credential);
+        }
+    }
+
+    /// Tells each helper to erase `credential`, which was rejected by the remote, then clears its username and secrets.
+    pub fn reject(&self, credential: &mut GitCredential) {
+        for helper in &self.helpers {
+            let _ = helper.erase(credential);
+        }
+        credential.username = None;
+        credential.password = None;
+        credential.credential = None;
+        credential.oauth_refresh_token = None;
+        credential.password_expiry_utc = None;
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for CredentialChain {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut chain = Self::new();
+        for helper in iter {
+            chain.add(helper.as_ref());
+        }
+        chain
+    }
+}
+
+/// Merges a helper's answer into `dst` the way git reads it: attributes that are present replace the current ones,
+/// and multi-valued attributes are appended.
+fn merge(dst: &mut GitCredential, src: GitCredential) {
+    merge_opt(&mut dst.protocol, src.protocol);
+    merge_opt(&mut dst.host, src.host);
+    merge_opt(&mut dst.path, src.path);
+    merge_opt(&mut dst.username, src.username);
+    merge_opt(&mut dst.password, src.password);
+    merge_opt(&mut dst.password_expiry_utc, src.password_expiry_utc);
+    merge_opt(&mut dst.oauth_refresh_token, src.oauth_refresh_token);
+    merge_opt(&mut dst.authtype, src.authtype);
+    merge_opt(&mut dst.credential, src.credential);
+    dst.ephemeral |= src.ephemeral;
+    dst.wwwauth.extend(src.wwwauth);
+    dst.state.extend(src.state);
+    dst.quit |= src.quit;
+}
+
+#[inline]
+fn merge_opt<T>(dst: &mut Option<T>, src: Option<T>) {
+    if src.is_some() {
+        *dst = src;
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::Secret;
+    use crate::test_util::{expose, request, script, temp_dir};
+
+    /// A helper that prints `output` for `get`.
+    fn answer(output: &str) -> String {
+        format!("!f() {{ cat >/dev/null; test \"$1\" = get && printf '{output}'; }}; f")
+    }
+
+    #[test]
+    fn merges_answers_until_complete() {
+        let dir = temp_dir("chain-merge");
+        let unused = script(&dir, "unused", &format!("touch {}/called", dir.display()));
+        let chain: CredentialChain = [
+            answer("username=first\\n"),
+            answer("username=second\\npath=repo\\n"),
+            "!exit 1".to_owned(),
+            answer("password=secret\\n"),
+            unused.display().to_string(),
+        ]
+        .into_iter()
+        .collect();
+        let mut credential = request();
+        chain.fill(&mut credential).unwrap();
+        assert_eq!(credential.username.as_deref(), Some("second"));
+        assert_eq!(credential.path.as_deref(), Some("repo"));
+        assert_eq!(expose(&credential.password), Some("secret"));
+        assert!(!dir.join("called").exists());
+    }
+
+    #[test]
+    fn stops_on_quit() {
+        let chain: CredentialChain = [answer("username=user\\nquit=1\\n"), answer("password=secret\\n")]
+            .into_iter()
+            .collect();
+        let mut credential = request();
+        assert!(matches!(chain.fill(&mut credential), Err(FillError::Quit { .. })));
+        assert_eq!(expose(&credential.password), None);
+    }
+
+    #[test]
+    fn drops_expired_password() {
+        let chain: CredentialChain = [
+            answer("username=user\\npassword=expired\\npassword_expiry_utc=1\\n"),
+            answer("password=fresh\\n"),
+        ]
+        .into_iter()
+        .collect();
+        let mut credential = request();
+        chain.fill(&mut credential).unwrap();
+        assert_eq!(expose(&credential.password), Some("fresh"));
+        assert_eq!(credential.password_expiry_utc, None);
+
+        let chain: CredentialChain = [answer("username=user\\npassword=expired\\npassword_expiry_utc=1\\n")]
+            .into_iter()
+            .collect();
+        let mut credential = request();
+        assert!(matches!(chain.fill(&mut credential), Err(FillError::NotFound)));
+    }
+
+    #[test]
+    fn drops_expired_credential() {
+        let chain: CredentialChain = [answer(
+            "capability[]=authtype\\nauthtype=Bearer\\ncredential=token\\npassword_expiry_utc=1\\n",
+        )]
+        .into_iter()
+        .collect();
+        let mut credential = request();
+        credential.capabilities = Capabilities::AUTHTYPE;
+        assert!(matches!(chain.fill(&mut credential), Err(FillError::NotFound)));
+        assert!(credential.credential.is_none());
+    }
+
+    #[test]
+    fn approves_and_rejects_with_store() {
+        let dir = temp_dir("chain-store");
+        let chain: CredentialChain = [format!("store --file={}", dir.join("credentials").display())]
+            .into_iter()
+            .collect();
+        let mut credential = request();
+        credential.username = Some("user".to_owned());
+        credential.password = Some(Secret::from("secret"));
+        chain.approve(&credential);
+
+        let mut filled = request();
+        chain.fill(&mut filled).unwrap();
+        assert_eq!(filled.username.as_deref(), Some("user"));
+        assert_eq!(expose(&filled.password), Some("secret"));
+
+        chain.reject(&mut credential);
+        assert_eq!(credential.username, None);
+        assert_eq!(expose(&credential.password), None);
+        assert!(matches!(chain.fill(&mut request()), Err(FillError::NotFound)));
+    }
+}
@@ -0,0 +1,218 @@
+// SPDX-FileCopyrightText: 2026 Antoni Szymański
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::{GitCredential, Secret, percent};
+use snafu::{OptionExt, Snafu, ensure};
+use std::borrow::Cow;
+
+#[derive(Debug, Snafu)]
+#[snafu(context(suffix(Ctx)))]
+#[non_exhaustive]
+pub enum CredentialUrlError {
+    #[snafu(display("URL has no scheme"))]
+    MissingScheme,
+    #[snafu(display("URL contains a newline or carriage return in its {component}"))]
+    ControlCharacter { component: &'static str },
+    #[snafu(display("URL {component} is not valid UTF-8 once percent-decoded"))]
+    InvalidUtf8 { component: &'static str },
+    #[snafu(
+        display("Remote helper {transport:?} is given an address that is not a URL"),
+        visibility(pub(crate))
+    )]
+    NotUrlAddress { transport: String },
+}
+
+impl GitCredential {
+    /// Parses a URL of the form `protocol://[user[:password]@]host[/path]` the way git's `credential_from_url_gently`
+    /// does, which is also how the `url` attribute is read.
+    ///
+    /// Unlike [`GitCredential::from_url`], this does not normalize the URL: each component is only percent-decoded.
+    /// Leading and trailing slashes are trimmed from the path, and an empty path is left unset.
+    pub fn from_url_str(url: &str) -> Result<Self, CredentialUrlError> {
+        let (protocol, rest) = url.split_once("://").context(MissingSchemeCtx)?;
+        ensure!(!protocol.is_empty(), MissingSchemeCtx);
+        // A query or fragment also ends the host.
+        let (authority, path) = rest.split_at(rest.find(['/', '?', '#']).unwrap_or(rest.len()));
+        let (userinfo, host) = match authority.split_once('@') {
+            Some((userinfo, host)) => (Some(userinfo), host),
+            None => (None, authority),
+        };
+        let (username, password) = match userinfo.map(|userinfo| userinfo.split_once(':').ok_or(userinfo)) {
+            Some(Ok((username, password))) => (Some(username), Some(password)),
+            Some(Err(username)) => (Some(username), None),
+            None => (None, None),
+        };
+
+        let path = decode(path.trim_start_matches('/'), "path")?;
+        let credential = Self {
+            protocol: Some(protocol.to_owned()),
+            host: Some(decode(host, "host")?.into_owned()),
+            path: trim_decoded_path(&path).map(str::to_owned),
+            username: username
+                .map(|username| decode(username, "username"))
+                .transpose()?
+                .map(Cow::into_owned),
+            password: password
+                .map(|password| decode(password, "password"))
+                .transpose()?
+                .map(|password| Secret::from(password.into_owned())),
+            ..Default::default()
+        };
+
+        check_components(&credential)?;
+        Ok(credential)
+    }
+}
+
+/// Percent-decodes a URL component, which must be valid UTF-8 once decoded.
+pub(crate) fn decode<'a>(value: &'a str, component: &'static str) -> Result<Cow<'a, str>, CredentialUrlError> {
+    percent::decode(value).context(InvalidUtf8Ctx { component })
+}
+
+/// Trims trailing slashes from a path whose leading slashes were trimmed before decoding. Like git, this never trims
+/// the first character, so that `%2F` stays `/`.
+pub(crate) fn trim_decoded_path(path: &str) -> Option<&str> {
+    match path.trim_end_matches('/') {
+        "" => Some(&path[..path.len().min(1)]).filter(|path| !path.is_empty()),
+        trimmed => Some(trimmed),
+    }
+}
+
+/// Rejects newlines and carriage returns, which would let a remote inject attributes into a credential request.
+pub(crate) fn check_components(credential: &GitCredential) -> Result<(), CredentialUrlError> {
+    let components = [
+        ("username", credential.username.as_deref()),
+        ("password", credential.password.as_ref().map(Secret::expose)),
+        ("protocol", credential.protocol.as_deref()),
+        ("host", credential.host.as_deref()),
+        ("path", credential.path.as_deref()),
+    ];
+    for (component, value) in components {
+        ensure!(
+            !value.is_some_and(|value| value.contains(['\n', '\r'])),
+            ControlCharacterCtx { component }
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CredentialUrlError, FromReaderError, GitCredential};
+
+    /// Parses `input` and writes the result back, with secrets, in the git-credential format.
+    fn roundtrip(input: &str) -> Result<String, FromReaderError> {
+        let credential: GitCredential = input.parse()?;
+        let mut output = Vec::new();
+        credential.to_writer(&mut output).unwrap();
+        Ok(String::from_utf8(output).unwrap())
+    }
+
+    /// Inputs and the output of `git credential fill` for them.
+    const TABLE: &[(&str, &str)] = &[
+        (
+            "username=before\npath=before\nurl=https://u@h/p\n",
+            "protocol=https\nhost=h\npath=p\nusername=u\n",
+        ),
+        (
+            "url=https://u@h/p\nusername=after\npath=after\n",
+            "protocol=https\nhost=h\npath=after\nusername=after\n",
+        ),
+        ("url=https://user@h\n", "protocol=https\nhost=h\nusername=user\n"),
+        (
+            "url=https://user:@h\n",
+            "protocol=https\nhost=h\nusername=user\npassword=\n",
+        ),
+        (
+            "url=https://:pw@h\n",
+            "protocol=https\nhost=h\nusername=\npassword=pw\n",
+        ),
+        (
+            "url=https://u:p:q@h:8443/a/b/\n",
+            "protocol=https\nhost=h:8443\npath=a/b\nusername=u\npassword=p:q\n",
+        ),
+        ("url=https://a@b@h\n", "protocol=https\nhost=b@h\nusername=a\n"),
+        ("url=https://h?x\n", "protocol=https\nhost=h\npath=?x\n"),
+        ("url=https://h?u@x\n", "protocol=https\nhost=h\npath=?u@x\n"),
+        ("url=https://h#f\n", "protocol=https\nhost=h\npath=#f\n"),
+        ("url=https:///path\n", "protocol=https\nhost=\npath=path\n"),
+        ("url=https://h//x//\n", "protocol=https\nhost=h\npath=x\n"),
+        ("url=https://h/%2F\n", "protocol=https\nhost=h\npath=/\n"),
+        ("url=https://h/%2F%2F/\n", "protocol=https\nhost=h\npath=/\n"),
+        (
+            "url=https://me%40corp:p%2Fw@h%41st/a%20b\n",
+            "protocol=https\nhost=hAst\npath=a b\nusername=me@corp\npassword=p/w\n",
+        ),
+    ];
+
+    #[test]
+    fn url_attribute_matches_git() {
+        for (input, expected) in TABLE {
+            assert_eq!(roundtrip(input).unwrap(), *expected, "input: {input:?}");
+        }
+    }
+
+    #[test]
+    fn url_attribute_rejects_control_characters() {
+        for input in ["url=https://u%0ax@h\n", "url=https://h/p%0d\n", "url=https://u:%0a@h\n"] {
+            let err = roundtrip(input).unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    FromReaderError::InvalidUrl {
+                        source: CredentialUrlError::ControlCharacter { .. },
+                        line_number: 1
+                    }
+                ),
+                "input: {input:?}, error: {err:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn url_attribute_rejects_invalid_utf8() {
+        for input in ["url=https://u:%FF@h\n", "url=https://h/%C3\n"] {
+            let err = roundtrip(input).unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    FromReaderError::InvalidUrl {
+                        source: CredentialUrlError::InvalidUtf8 { .. },
+                        ..
+                    }
+                ),
+                "input: {input:?}, error: {err:?}"
+            );
+        }
+    }
+
+    #[cfg(feature = "url")]
+    #[test]
+    fn set_url_rejects_invalid_utf8() {
+        let mut credential = GitCredential::from_url(&url::Url::parse("https://u:p%2Fw@h/%2F").unwrap()).unwrap();
+        assert_eq!(credential.password.as_ref().map(crate::Secret::expose), Some("p/w"));
+        assert_eq!(credential.path.as_deref(), Some("/"));
+        let err = credential
+            .set_url(&url::Url::parse("https://other:%FF@h").unwrap())
+            .unwrap_err();
+        assert!(matches!(err, CredentialUrlError::InvalidUtf8 { component: "password" }));
+        assert_eq!(credential.username.as_deref(), Some("u"));
+    }
+
+    #[test]
+    fn url_attribute_requires_scheme() {
+        for input in ["url=nothing\n", "url=://x\n", "url=h/p\n"] {
+            let err = roundtrip(input).unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    FromReaderError::InvalidUrl {
+                        source: CredentialUrlError::MissingScheme,
+                        ..
+                    }
+                ),
+                "input: {input:?}, error: {err:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,94 @@
+// SPDX-FileCopyrightText: 2026 Antoni Szymański
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::{Capabilities, FromReaderError, GitCredential, ToWriterError};
+use snafu::{OptionExt, ResultExt, Snafu};
+use std::error::Error;
+use std::io::{self, Read, Write};
+use std::process::ExitCode;
+
+/// A credential helper that can be run with [`run_helper`].
+pub trait Helper {
+    type Error: Error + 'static;
+
+    /// The capabilities this helper supports. Only those that git announced as well are used.
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::empty()
+    }
+
+    /// Returns the credential matching `request`, or `None` if this helper has none.
+    fn get(&mut self, request: &GitCredential) -> Result<Option<GitCredential>, Self::Error>;
+
+    /// Stores `credential` for later use. Does nothing by default.
+    fn store(&mut self, credential: &GitCredential) -> Result<(), Self::Error> {
+        let _ = credential;
+        Ok(())
+    }
+
+    /// Erases the credentials matching `credential`. Does nothing by default.
+    fn erase(&mut self, credential: &GitCredential) -> Result<(), Self::Error> {
+        let _ = credential;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(context(suffix(Ctx)))]
+#[non_exhaustive]
+pub enum RunHelperError<E: Error + 'static> {
+    #[snafu(display("Missing operation (expected get, store or erase)"))]
+    MissingOperation,
+    #[snafu(display("Failed to read credential from input"))]
+    ReadCredential { source: FromReaderError },
+    #[snafu(display("Failed to write credential to output"))]
+    WriteCredential { source: ToWriterError },
+    #[snafu(display("Helper failed to {operation} credential"))]
+    Operation { source: E, operation: &'static str },
+}
+
+/// Runs `helper` as the `main` of a credential helper program.
+///
+/// The operation is taken from the last command-line argument, since git appends it after any arguments given in
+/// `credential.helper`. The credential is read from stdin and the answer to `get` is written to stdout. Unknown
+/// operations are ignored, as git requires. Errors are printed to stderr and reported with a failure exit code.
+pub fn run_helper(mut helper: impl Helper) -> ExitCode {
+    let operation = std::env::args_os().skip(1).last();
+    let operation = operation.as_ref().map(|s| s.to_string_lossy());
+    match run_helper_with(
+        &mut helper,
+        operation.as_deref(),
+        io::stdin().lock(),
+        io::stdout().lock(),
+    ) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", snafu::Report::from_error(err));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Runs a single `operation` of `helper`, reading the credential from `input` and writing the answer to `output`.
+pub fn run_helper_with<H: Helper>(
+    helper: &mut H,
+    operation: Option<&str>,
+    input: impl Read,
+    mut output: impl Write,
+) -> Result<(), RunHelperError<H::Error>> {
+    let operation = operation.context(MissingOperationCtx)?;
+    let mut request = GitCredential::from_reader(input).context(ReadCredentialCtx)?;
+    request.negotiate(helper.capabilities());
+    match operation {
+        "get" => {
+            if let Some(mut response) = helper.get(&request).context(OperationCtx { operation: "get" })? {
+                response.capabilities = request.capabilities;
+                response.to_writer(&mut output).context(WriteCredentialCtx)?;
+                output.flush().context(crate::WriteCtx).context(WriteCredentialCtx)?;
+            }
+        }
+        "store" => helper.store(&request).context(OperationCtx { operation: "store" })?,
+        "erase" => helper.erase(&request).context(OperationCtx { operation: "erase" })?,
+        _ => {}
+    }
+    Ok(())
+}
@@ -0,0 +1,173 @@
+// SPDX-FileCopyrightText: 2026 Antoni Szymański
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::{FromReaderError, GitCredential, ToWriterError};
+use snafu::{ResultExt, Snafu};
+use std::io;
+use std::path::Path;
+use std::process::{Command, ExitStatus, Stdio};
+
+/// A credential helper as configured with `credential.helper`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HelperInvocation {
+    command: String,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(context(suffix(Ctx)))]
+#[non_exhaustive]
+pub enum InvocationError {
+    #[snafu(display("Failed to run credential helper {command:?}"))]
+    Spawn { source: io::Error, command: String },
+    #[snafu(display("Failed to write credential to helper {command:?}"))]
+    WriteCredential { source: ToWriterError, command: String },
+    #[snafu(display("Failed to read credential from helper {command:?}"))]
+    ReadCredential { source: FromReaderError, command: String },
+    #[snafu(display("Failed to wait for credential helper {command:?}"))]
+    Wait { source: io::Error, command: String },
+    #[snafu(display("Credential helper {command:?} failed with {status}"))]
+    Status { status: ExitStatus, command: String },
+}
+
+impl HelperInvocation {
+    /// Resolves a `credential.helper` value the way git does.
+    ///
+    /// A value starting with `!` is run as a shell snippet, an absolute path is run as is, and anything else is run as
+    /// `git credential-<name>`, so that git finds the helper in its exec-path (where `store` and `cache` live) or in
+    /// `PATH`. In all cases, the value may be followed by arguments.
+    pub fn new(helper: &str) -> Self {
+        let command = if let Some(snippet) = helper.strip_prefix('!') {
+            snippet.to_owned()
+        } else if Path::new(helper).is_absolute() {
+            helper.to_owned()
+        } else {
+            format!("git credential-{helper}")
+        };
+        Self { command }
+    }
+
+    /// The shell command the operation is appended to.
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+
+    /// Asks the helper for a credential matching `credential` and returns its answer.
+    pub fn get(&self, credential: &GitCredential) -> Result<GitCredential, InvocationError> {
+        self.run("get", credential, true).map(Option::unwrap_or_default)
+    }
+
+    /// Asks the helper to store `credential`.
+    pub fn store(&self, credential: &GitCredential) -> Result<(), InvocationError> {
+        self.run("store", credential, false).map(drop)
+    }
+
+    /// Asks the helper to erase the credentials matching `credential`.
+    pub fn erase(&self, credential: &GitCredential) -> Result<(), InvocationError> {
+        self.run("erase", credential, false).map(drop)
+    }
+
+    fn run(
+        &self,
+        operation: &str,
+        credential: &GitCredential,
+        want_output: bool,
+    ) -> Result<Option<GitCredential>, InvocationError> {
+        let command = &self.command;
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(format!("{command} {operation}"))
+            .stdin(Stdio::piped())
+            .stdout(if want_output { Stdio::piped() } else { Stdio::null() })
+            .spawn()
+            .context(SpawnCtx { command })?;
+
+        // The helper may exit without reading its input, which is not an error.
+        let stdin = child.stdin.take().expect("stdin is piped");
+        match credential.to_writer(stdin) {
+            Err(ToWriterError::Write { source }) if source.kind() == io::ErrorKind::BrokenPipe => {}
+            Err(err) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(err).context(WriteCredentialCtx { command });
+            }
+            Ok(()) => {}
+        }
+
+        let output = match child.stdout.take() {
+            Some(stdout) => match GitCredential::from_reader(stdout) {
+                Ok(output) => Some(output),
+                Err(err) => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(err).context(ReadCredentialCtx { command });
+                }
+            },
+            None => None,
+        };
+
+        let status = child.wait().context(WaitCtx { command })?;
+        if !status.success() {
+            return StatusCtx { status, command }.fail();
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::test_util::{expose, request, script, temp_dir};
+
+    #[test]
+    fn shell_snippet() {
+        let helper = HelperInvocation::new("!f() { echo username=$1; echo password=secret; }; f");
+        let answer = helper.get(&request()).unwrap();
+        assert_eq!(answer.username.as_deref(), Some("get"));
+        assert_eq!(expose(&answer.password), Some("secret"));
+    }
+
+    #[test]
+    fn absolute_path_with_args() {
+        let dir = temp_dir("absolute-path");
+        let path = script(&dir, "helper", "cat >/dev/null; echo username=$1; echo password=$2");
+        let helper = HelperInvocation::new(&format!("{} --flag", path.display()));
+        let answer = helper.get(&request()).unwrap();
+        assert_eq!(answer.username.as_deref(), Some("--flag"));
+        assert_eq!(expose(&answer.password), Some("get"));
+    }
+
+    #[test]
+    fn name_with_args() {
+        let dir = temp_dir("name-with-args");
+        let helper = HelperInvocation::new(&format!("store --file={}", dir.join("credentials").display()));
+        assert_eq!(
+            helper.command(),
+            format!("git credential-store --file={}", dir.join("credentials").display())
+        );
+        let credential = GitCredential::builder()
+            .protocol("https")
+            .host("example.com")
+            .username("user")
+            .password("secret")
+            .build();
+        helper.store(&credential).unwrap();
+        let answer = helper.get(&request()).unwrap();
+        assert_eq!(answer.username.as_deref(), Some("user"));
+        assert_eq!(expose(&answer.password), Some("secret"));
+        helper.erase(&credential).unwrap();
+        assert_eq!(helper.get(&request()).unwrap().username, None);
+    }
+
+    #[test]
+    fn helper_exits_without_reading_input() {
+        // Large enough to fill the pipe, so that writing fails once the helper has exited.
+        let mut credential = request();
+        credential.wwwauth = vec!["x".repeat(1000); 1000];
+        let answer = HelperInvocation::new("!exit 0").get(&credential).unwrap();
+        assert_eq!(answer.username, None);
+        assert!(matches!(
+            HelperInvocation::new("!exit 3").get(&credential),
+            Err(InvocationError::Status { .. })
+        ));
+    }
+}
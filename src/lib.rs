@@ -1,12 +1,56 @@
 // SPDX-FileCopyrightText: 2026 Antoni Szymański
 // SPDX-License-Identifier: MPL-2.0
 
-use snafu::{ResultExt, Snafu};
-use std::io::{self, BufRead, BufReader, Read, Write};
+use snafu::{OptionExt, ResultExt, Snafu, ensure};
+use std::borrow::Cow;
+use std::fmt::{self, Write as _};
+use std::io::{self, BufRead, Read, Write};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 #[cfg(feature = "url")]
 use url::Url;
+#[cfg(feature = "url")]
+use zeroize::Zeroizing;
+
+#[cfg(feature = "tokio")]
+mod async_io;
+mod builder;
+#[cfg(unix)]
+mod cache;
+mod capabilities;
+mod chain;
+mod credential_url;
+mod helper;
+mod invocation;
+mod matching;
+mod normalize;
+mod parse;
+mod percent;
+mod remote;
+mod secret;
+#[cfg(feature = "serde")]
+mod serialize;
+mod store;
+#[cfg(test)]
+mod test_util;
 
-#[derive(Debug, Default)]
+pub use builder::GitCredentialBuilder;
+#[cfg(unix)]
+pub use cache::{CacheClient, CacheDaemon, CacheError, run_cache_client, run_cache_daemon};
+pub use capabilities::Capabilities;
+pub use chain::{CredentialChain, FillError};
+pub use credential_url::CredentialUrlError;
+pub use helper::{Helper, RunHelperError, run_helper, run_helper_with};
+pub use invocation::{HelperInvocation, InvocationError};
+pub use matching::MatchOptions;
+pub use normalize::NormalizeOptions;
+pub use parse::{ParseOptions, Records};
+pub use secret::Secret;
+#[cfg(feature = "serde")]
+pub use serialize::ExposeSecrets;
+pub use store::{CredentialStore, StoreError};
+
+#[derive(Debug, Default, Clone)]
 pub struct GitCredential {
     /// The protocol over which the credential will be used (e.g., https).
     pub protocol: Option<String>,
@@ -17,91 +61,316 @@ pub struct GitCredential {
     /// The credential’s username, if we already have one (e.g., from a URL, the configuration, the user, or from a previously run helper).
     pub username: Option<String>,
     /// The credential’s password, if we are asking it to be stored.
-    pub password: Option<String>,
+    pub password: Option<Secret>,
+    /// The expiry date of a generated password such as an OAuth access token. Sent as Unix time in seconds.
+    pub password_expiry_utc: Option<SystemTime>,
+    /// An OAuth refresh token that may accompany a password that is an OAuth access token. It is as confidential as the password.
+    pub oauth_refresh_token: Option<Secret>,
+    /// The authentication scheme to use (e.g., "Bearer"). If `credential` is set, this is mandatory.
+    pub authtype: Option<String>,
+    /// The pre-encoded credential, suitable for the protocol in question. If set, `username` and `password` are not used.
+    pub credential: Option<Secret>,
+    /// Whether `credential` is only useful for a limited time and should not be stored by a helper.
+    pub ephemeral: bool,
+    /// The capabilities announced by the sender. Attributes that depend on a capability are only written if it is present.
+    pub capabilities: Capabilities,
+    /// The `WWW-Authenticate` headers received from the server, in order.
+    pub wwwauth: Vec<String>,
+    /// Opaque state a helper wants to receive again in the next stage of a multistage authentication.
+    pub state: Vec<String>,
+    /// Whether git should stop consulting further helpers and give up on this credential.
+    pub quit: bool,
+    /// Attributes not known to this crate, in the order they were read. They are written after all the others.
+    pub extra: Vec<(String, String)>,
 }
 
+/// An error from parsing a credential. Errors name the line and, where there is one, the key, but never include a
+/// value, which could be a secret.
 #[derive(Debug, Snafu)]
 #[snafu(context(suffix(Ctx)))]
 #[non_exhaustive]
 pub enum FromReaderError {
-    #[snafu(display("Failed to read line from input reader"))]
-    ReadLine { source: io::Error },
-    #[snafu(display("Line exceeds {MAX_LINE_LENGTH} bytes limit"))]
-    TooLongLine,
-    #[snafu(display("Failed to parse line (expected a key-value pair): {line:?}"))]
-    InvalidLine { line: String },
-    #[cfg(feature = "url")]
-    #[snafu(display("Failed to parse URL: {input:?}"))]
-    InvalidUrl { source: url::ParseError, input: String },
+    #[snafu(display("Failed to read line {line_number} from input reader"))]
+    ReadLine { source: io::Error, line_number: usize },
+    #[snafu(display("Line {line_number}{} exceeds {MAX_LINE_LENGTH} bytes limit", fmt_key(key)))]
+    TooLongLine { line_number: usize, key: Option<String> },
+    #[snafu(display("Failed to parse line {line_number} (expected a key-value pair)"))]
+    InvalidLine { line_number: usize },
+    #[snafu(display("Line {line_number}{} is not valid UTF-8", fmt_key(key)))]
+    InvalidUtf8 { line_number: usize, key: Option<String> },
+    #[snafu(display("Failed to parse value of {key:?} on line {line_number} as a boolean"))]
+    InvalidBool { line_number: usize, key: String },
+    #[snafu(display("Failed to parse value of \"url\" on line {line_number}"))]
+    InvalidUrl {
+        source: CredentialUrlError,
+        line_number: usize,
+    },
+    #[snafu(display("Duplicate key {key:?} on line {line_number}"))]
+    DuplicateKey { line_number: usize, key: String },
+    #[snafu(display("Unknown key {key:?} on line {line_number}"))]
+    UnknownKey { line_number: usize, key: String },
+    #[snafu(display("Input ended after line {line_number} without a blank line"))]
+    MissingTerminator { line_number: usize },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(context(suffix(Ctx)))]
+#[non_exhaustive]
+pub enum ToWriterError {
+    #[snafu(display("Failed to write line to output writer"))]
+    Write { source: io::Error },
+    #[snafu(display("Key must be non-empty and must not contain '=', a newline or a NUL byte: {key:?}"))]
+    InvalidKey { key: String },
+    #[snafu(display("Value of {key:?} must not contain a newline or a NUL byte"))]
+    InvalidValue { key: String },
+}
+
+#[cfg(feature = "url")]
+#[derive(Debug, Snafu)]
+#[snafu(context(suffix(Ctx)))]
+#[non_exhaustive]
+pub enum ToUrlError {
+    #[snafu(display("Credential has no protocol"))]
+    MissingProtocol,
+    #[snafu(display("Failed to build URL from credential"))]
+    BuildUrl { source: url::ParseError },
+    #[snafu(display("URL cannot have a username or password"))]
+    CannotHaveCredentials,
 }
 
 const MAX_LINE_LENGTH: usize = 65535 - 1;
 
 impl GitCredential {
+    /// Reads a credential, accepting everything git accepts. See [`ParseOptions`] for stricter parsing.
+    ///
+    /// This may consume bytes after the blank line that ends the credential. Use
+    /// [`GitCredential::from_buf_reader`] to read further data from the same stream.
     pub fn from_reader(reader: impl Read) -> Result<Self, FromReaderError> {
-        let mut gc = Self::default();
-        let buf_reader = BufReader::new(reader);
-        for line in buf_reader.lines() {
-            let line = line.context(ReadLineCtx)?;
-            if line.is_empty() {
-                break;
-            } else if line.len() > MAX_LINE_LENGTH {
-                return Err(FromReaderError::TooLongLine);
+        ParseOptions::lenient().parse(reader)
+    }
+
+    /// Reads a credential, consuming nothing after the blank line that ends it.
+    pub fn from_buf_reader(reader: &mut impl BufRead) -> Result<Self, FromReaderError> {
+        ParseOptions::lenient().parse_buf(reader)
+    }
+
+    /// Returns an iterator over the credentials in `reader`, each ending with a blank line.
+    pub fn records<R: BufRead>(reader: R) -> Records<R> {
+        ParseOptions::lenient().records(reader)
+    }
+
+    /// Sets a single attribute. Returns `false` if the key is not known, in which case nothing is set.
+    fn set_attribute(&mut self, key: &str, value: &str, line_number: usize) -> Result<bool, FromReaderError> {
+        match key {
+            "protocol" => put_str(&mut self.protocol, value),
+            "host" => put_str(&mut self.host, value),
+            "path" => put_str(&mut self.path, value),
+            "username" => put_str(&mut self.username, value),
+            "password" => put_secret(&mut self.password, value),
+            "password_expiry_utc" => self.password_expiry_utc = parse_timestamp(value),
+            "oauth_refresh_token" => put_secret(&mut self.oauth_refresh_token, value),
+            "authtype" => put_str(&mut self.authtype, value),
+            "credential" => put_secret(&mut self.credential, value),
+            "ephemeral" => self.ephemeral = parse_bool(value).context(InvalidBoolCtx { line_number, key })?,
+            "capability[]" => put_capability(&mut self.capabilities, value),
+            "wwwauth[]" => put_array(&mut self.wwwauth, value),
+            "state[]" => put_array(&mut self.state, value),
+            "quit" => self.quit = parse_bool(value).context(InvalidBoolCtx { line_number, key })?,
+            // Like git, this discards every attribute read so far.
+            "url" => *self = Self::from_url_str(value).context(InvalidUrlCtx { line_number })?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Writes the credential in the git-credential format.
+    ///
+    /// Every attribute is validated before anything is written, so that a value containing a newline cannot forge
+    /// further attributes.
+    pub fn to_writer(&self, mut writer: impl Write) -> Result<(), ToWriterError> {
+        let attributes = self.attributes();
+        for (key, value) in &attributes {
+            ensure!(
+                !key.is_empty() && !key.contains(['=', '\n', '\0']),
+                InvalidKeyCtx { key: *key }
+            );
+            ensure!(!value.contains(['\n', '\0']), InvalidValueCtx { key: *key });
+        }
+        for (key, value) in &attributes {
+            writeln!(writer, "{key}={value}").context(WriteCtx)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the attributes to write, in the order git writes them.
+    fn attributes(&self) -> Vec<(&str, Cow<'_, str>)> {
+        let mut attributes = Vec::new();
+        for capability in self.capabilities.names() {
+            attributes.push(("capability[]", Cow::Borrowed(capability)));
+        }
+        if self.capabilities.contains(Capabilities::AUTHTYPE) {
+            push_opt(&mut attributes, "authtype", self.authtype.as_deref());
+            push_opt(
+                &mut attributes,
+                "credential",
+                self.credential.as_ref().map(Secret::expose),
+            );
+            if self.ephemeral {
+                attributes.push(("ephemeral", Cow::Borrowed("1")));
             }
-            let (key, value) = match line.split_once('=') {
-                Some(v) => v,
-                None => return Err(FromReaderError::InvalidLine { line }),
-            };
-            match key {
-                "protocol" => put_str(&mut gc.protocol, value),
-                "host" => put_str(&mut gc.host, value),
-                "path" => put_str(&mut gc.path, value),
-                "username" => put_str(&mut gc.username, value),
-                "password" => put_str(&mut gc.password, value),
-                #[cfg(feature = "url")]
-                "url" => gc.set_url(&Url::parse(value).context(InvalidUrlCtx { input: value })?),
-                _ => {}
+        }
+        push_opt(&mut attributes, "protocol", self.protocol.as_deref());
+        push_opt(&mut attributes, "host", self.host.as_deref());
+        push_opt(&mut attributes, "path", self.path.as_deref());
+        push_opt(&mut attributes, "username", self.username.as_deref());
+        push_opt(&mut attributes, "password", self.password.as_ref().map(Secret::expose));
+        push_opt(
+            &mut attributes,
+            "oauth_refresh_token",
+            self.oauth_refresh_token.as_ref().map(Secret::expose),
+        );
+        if let Some(password_expiry_utc) = self.password_expiry_utc {
+            let value = format_timestamp(password_expiry_utc).to_string();
+            attributes.push(("password_expiry_utc", Cow::Owned(value)));
+        }
+        for wwwauth in &self.wwwauth {
+            attributes.push(("wwwauth[]", Cow::Borrowed(wwwauth)));
+        }
+        if self.capabilities.contains(Capabilities::STATE) {
+            for state in &self.state {
+                attributes.push(("state[]", Cow::Borrowed(state)));
             }
         }
+        if self.quit {
+            attributes.push(("quit", Cow::Borrowed("1")));
+        }
+        for (key, value) in &self.extra {
+            attributes.push((key, Cow::Borrowed(value)));
+        }
+        attributes
+    }
+
+    /// Restricts `capabilities` to those that are also `supported`, so that only attributes both sides understand are written back.
+    pub fn negotiate(&mut self, supported: Capabilities) {
+        self.capabilities = self.capabilities & supported;
+    }
+
+    /// Creates a credential from `url`. See [`GitCredential::set_url`].
+    #[cfg(feature = "url")]
+    pub fn from_url(url: &Url) -> Result<Self, CredentialUrlError> {
+        let mut gc = Self::default();
+        gc.set_url(url)?;
         Ok(gc)
     }
 
-    pub fn to_writer(&self, mut writer: impl Write) -> Result<(), io::Error> {
-        if let Some(protocol) = &self.protocol {
-            writeln!(writer, "protocol={protocol}")?;
-        }
-        if let Some(host) = &self.host {
-            writeln!(writer, "host={host}")?;
+    /// Sets `protocol`, `host`, `path`, `username` and `password` from `url`, percent-decoding them the way git's
+    /// `credential_from_url` does. Leading and trailing slashes are trimmed from the path, and an empty path or
+    /// username is left unset.
+    ///
+    /// Fails without changing the credential if a component is not valid UTF-8 once decoded.
+    #[cfg(feature = "url")]
+    pub fn set_url(&mut self, url: &Url) -> Result<(), CredentialUrlError> {
+        let host = url
+            .host_str()
+            .map(|host| credential_url::decode(host, "host"))
+            .transpose()?;
+        // Like git, trim leading slashes before decoding and trailing slashes after.
+        let path = credential_url::decode(url.path().trim_start_matches('/'), "path")?;
+        let username = credential_url::decode(url.username(), "username")?;
+        let password = url
+            .password()
+            .map(|password| credential_url::decode(password, "password"))
+            .transpose()?
+            .map(|password| Zeroizing::new(password.into_owned()));
+
+        put_str(&mut self.protocol, url.scheme());
+        match (host, url.port()) {
+            (Some(host), Some(port)) => put_str(&mut self.host, &format!("{host}:{port}")),
+            (host, _) => put_opt_str(&mut self.host, host.as_deref()),
         }
+        put_opt_str(&mut self.path, credential_url::trim_decoded_path(&path));
+        put_opt_str(&mut self.username, Some(&*username).filter(|s| !s.is_empty()));
+        put_opt_secret(&mut self.password, password.as_ref().map(|s| s.as_str()));
+        Ok(())
+    }
+
+    /// Builds a URL from `protocol`, `host`, `path`, `username` and `password`, percent-encoding them the way
+    /// `git credential-store` does.
+    #[cfg(feature = "url")]
+    pub fn to_url(&self) -> Result<Url, ToUrlError> {
+        let protocol = self.protocol.as_deref().context(MissingProtocolCtx)?;
+        let host = percent::encode(
+            self.host.as_deref().unwrap_or_default(),
+            percent::is_reserved_or_unreserved,
+        );
+        let mut url = Url::parse(&format!("{protocol}://{host}/")).context(BuildUrlCtx)?;
         if let Some(path) = &self.path {
-            writeln!(writer, "path={path}")?;
+            url.set_path(&percent::encode(path, percent::is_reserved_or_unreserved));
         }
         if let Some(username) = &self.username {
-            writeln!(writer, "username={username}")?;
+            url.set_username(&percent::encode(username, percent::is_unreserved))
+                .ok()
+                .context(CannotHaveCredentialsCtx)?;
         }
         if let Some(password) = &self.password {
-            writeln!(writer, "password={password}")?;
+            let password = Zeroizing::new(percent::encode(password.expose(), percent::is_unreserved).into_owned());
+            url.set_password(Some(&password))
+                .ok()
+                .context(CannotHaveCredentialsCtx)?;
+        }
+        Ok(url)
+    }
+}
+
+impl FromStr for GitCredential {
+    type Err = FromReaderError;
+
+    /// Parses a credential the way [`GitCredential::from_reader`] does.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_reader(s.as_bytes())
+    }
+}
+
+/// Prints the credential in the git-credential format, with `password`, `oauth_refresh_token` and `credential`
+/// redacted as `***`.
+///
+/// Control characters in keys and values are escaped as in Rust string literals (`\n`, `\u{0}`), so that a value
+/// cannot forge additional lines. Use [`GitCredential::to_writer`] for output that git reads.
+impl fmt::Display for GitCredential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (key, value) in self.attributes() {
+            let value = match key {
+                "password" | "oauth_refresh_token" | "credential" => "***",
+                _ => &value,
+            };
+            write_escaped(f, key)?;
+            f.write_str("=")?;
+            write_escaped(f, value)?;
+            f.write_str("\n")?;
         }
         Ok(())
     }
+}
 
-    #[cfg(feature = "url")]
-    pub fn from_url(url: &Url) -> Self {
-        let mut gc = Self::default();
-        gc.set_url(url);
-        gc
+fn write_escaped(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    for c in s.chars() {
+        if c.is_control() {
+            write!(f, "{}", c.escape_debug())?;
+        } else {
+            f.write_char(c)?;
+        }
     }
+    Ok(())
+}
 
-    #[cfg(feature = "url")]
-    pub fn set_url(&mut self, url: &Url) {
-        put_str(&mut self.protocol, url.scheme());
-        put_opt_str(&mut self.host, url.host_str());
-        put_str(&mut self.path, trim_prefix(url.path(), "/"));
-        put_opt_str(&mut self.username, Some(url.username()).filter(|s| !s.is_empty()));
-        put_opt_str(&mut self.password, url.password());
+#[inline]
+fn push_opt<'a>(attributes: &mut Vec<(&'a str, Cow<'a, str>)>, key: &'a str, value: Option<&'a str>) {
+    if let Some(value) = value {
+        attributes.push((key, Cow::Borrowed(value)));
     }
 }
 
+#[cfg(feature = "url")]
 #[inline]
 fn put_opt_str(dst: &mut Option<String>, src: Option<&str>) {
     match src {
@@ -110,6 +379,23 @@ fn put_opt_str(dst: &mut Option<String>, src: Option<&str>) {
     }
 }
 
+#[cfg(feature = "url")]
+#[inline]
+fn put_opt_secret(dst: &mut Option<Secret>, src: Option<&str>) {
+    match src {
+        Some(src) => put_secret(dst, src),
+        None => *dst = None,
+    }
+}
+
+#[inline]
+fn put_secret(dst: &mut Option<Secret>, src: &str) {
+    match dst {
+        Some(dst) => dst.set(src),
+        None => *dst = Some(Secret::from(src)),
+    }
+}
+
 #[inline]
 fn put_str(dst: &mut Option<String>, src: &str) {
     match dst {
@@ -118,7 +404,136 @@ fn put_str(dst: &mut Option<String>, src: &str) {
     }
 }
 
+/// Appends to a multi-valued attribute, or clears it if the value is empty.
+#[inline]
+fn put_array(dst: &mut Vec<String>, src: &str) {
+    if src.is_empty() {
+        dst.clear();
+    } else {
+        dst.push(src.to_owned());
+    }
+}
+
+/// Adds a known capability to the set, or clears it if the value is empty. Unknown capabilities are ignored.
 #[inline]
-fn trim_prefix<'a>(s: &'a str, prefix: &'a str) -> &'a str {
-    s.strip_prefix(prefix).unwrap_or(s)
+fn put_capability(dst: &mut Capabilities, src: &str) {
+    if src.is_empty() {
+        *dst = Capabilities::empty();
+    } else if let Some(capability) = Capabilities::from_name(src) {
+        dst.insert(capability);
+    }
+}
+
+fn fmt_key(key: &Option<String>) -> String {
+    key.as_ref().map(|key| format!(" (key {key:?})")).unwrap_or_default()
+}
+
+/// Parses a Unix timestamp the way git does, treating `0` and invalid values as "never expires".
+fn parse_timestamp(s: &str) -> Option<SystemTime> {
+    s.parse().ok().and_then(timestamp_from_secs)
+}
+
+fn timestamp_from_secs(secs: u64) -> Option<SystemTime> {
+    match secs {
+        0 => None,
+        secs => UNIX_EPOCH.checked_add(Duration::from_secs(secs)),
+    }
+}
+
+fn format_timestamp(t: SystemTime) -> u64 {
+    t.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+/// Parses a boolean the way `git config` does.
+fn parse_bool(s: &str) -> Option<bool> {
+    if s.is_empty() || s.eq_ignore_ascii_case("false") || s.eq_ignore_ascii_case("no") || s.eq_ignore_ascii_case("off")
+    {
+        Some(false)
+    } else if s.eq_ignore_ascii_case("true") || s.eq_ignore_ascii_case("yes") || s.eq_ignore_ascii_case("on") {
+        Some(true)
+    } else {
+        s.parse::<i64>().ok().map(|n| n != 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::request;
+
+    #[test]
+    fn to_writer_rejects_newline_in_value() {
+        let mut credential = request();
+        credential.username = Some("user\npassword=forged".to_owned());
+        let mut output = Vec::new();
+        let err = credential.to_writer(&mut output).unwrap_err();
+        assert!(matches!(err, ToWriterError::InvalidValue { key } if key == "username"));
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn to_writer_rejects_nul_in_value() {
+        let mut credential = request();
+        credential.path = Some("repo\0".to_owned());
+        let err = credential.to_writer(io::sink()).unwrap_err();
+        assert!(matches!(err, ToWriterError::InvalidValue { key } if key == "path"));
+    }
+
+    #[test]
+    fn to_writer_rejects_equals_in_key() {
+        let mut credential = request();
+        credential.extra.push(("password=forged".to_owned(), "x".to_owned()));
+        let mut output = Vec::new();
+        let err = credential.to_writer(&mut output).unwrap_err();
+        assert!(matches!(err, ToWriterError::InvalidKey { key } if key == "password=forged"));
+        assert!(output.is_empty());
+    }
+
+    #[cfg(feature = "url")]
+    #[test]
+    fn url_round_trip() {
+        // The URL, the host and path read from it, and the URL built back from them.
+        let table = [
+            (
+                "https://example.com:8443/r",
+                "example.com:8443",
+                "https://example.com:8443/r",
+            ),
+            ("https://example.com:443/r", "example.com", "https://example.com/r"),
+            ("http://example.com:80/r", "example.com", "http://example.com/r"),
+            (
+                "http://example.com:443/r",
+                "example.com:443",
+                "http://example.com:443/r",
+            ),
+            ("https://[::1]/r", "[::1]", "https://[::1]/r"),
+            ("https://[::1]:8443/r", "[::1]:8443", "https://[::1]:8443/r"),
+        ];
+        for (input, host, output) in table {
+            let credential = GitCredential::from_url(&Url::parse(input).unwrap()).unwrap();
+            assert_eq!(credential.host.as_deref(), Some(host), "input: {input}");
+            assert_eq!(credential.path.as_deref(), Some("r"), "input: {input}");
+            assert_eq!(credential.to_url().unwrap().as_str(), output, "input: {input}");
+        }
+
+        let url = "https://me%40corp:p%2Fw@[::1]:8443/r";
+        let credential = GitCredential::from_url(&Url::parse(url).unwrap()).unwrap();
+        assert_eq!(credential.username.as_deref(), Some("me@corp"));
+        assert_eq!(credential.password.as_ref().map(Secret::expose), Some("p/w"));
+        assert_eq!(credential.to_url().unwrap().as_str(), url);
+    }
+
+    #[test]
+    fn display_redacts_and_escapes() {
+        let credential = GitCredential::builder()
+            .protocol("https")
+            .host("example.com")
+            .username("user\npassword=forged\r\0")
+            .password("secret")
+            .build();
+        assert_eq!(
+            credential.to_string(),
+            "protocol=https\nhost=example.com\nusername=user\\npassword=forged\\r\\0\npassword=***\n"
+        );
+    }
 }
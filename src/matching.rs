@@ -0,0 +1,145 @@
+// SPDX-FileCopyrightText: 2026 Antoni Szymański
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::{GitCredential, NormalizeOptions};
+
+/// How helpers match stored credentials against a query. git has already dropped the path from the query if
+/// `credential.useHttpPath` is off, so the path is always compared.
+pub(crate) const HELPER_MATCH: MatchOptions = MatchOptions::new().use_http_path(true);
+
+/// Options for [`GitCredential::matches`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MatchOptions {
+    use_http_path: bool,
+    match_password: bool,
+    normalize: Option<NormalizeOptions>,
+}
+
+impl MatchOptions {
+    pub const fn new() -> Self {
+        Self {
+            use_http_path: false,
+            match_password: false,
+            normalize: None,
+        }
+    }
+
+    /// Whether to compare the path of HTTP and HTTPS credentials, like `credential.useHttpPath`. The path of other
+    /// protocols is always compared.
+    pub const fn use_http_path(mut self, yes: bool) -> Self {
+        self.use_http_path = yes;
+        self
+    }
+
+    /// Whether to compare the password and the pre-encoded `credential` as well.
+    pub const fn match_password(mut self, yes: bool) -> Self {
+        self.match_password = yes;
+        self
+    }
+
+    /// Compares both credentials after normalizing them with `options`, instead of exactly like git. See
+    /// [`GitCredential::normalized_with`].
+    pub const fn normalize(mut self, options: NormalizeOptions) -> Self {
+        self.normalize = Some(options);
+        self
+    }
+}
+
+impl GitCredential {
+    /// Whether this credential satisfies `query`, following git's `credential_match`.
+    ///
+    /// Attributes missing from `query` match anything, while attributes present in `query` must be present in this
+    /// credential with the same value.
+    pub fn matches(&self, query: &GitCredential, options: MatchOptions) -> bool {
+        if let Some(normalize) = options.normalize {
+            let options = MatchOptions {
+                normalize: None,
+                ..options
+            };
+            return self
+                .normalized_with(normalize)
+                .matches(&query.normalized_with(normalize), options);
+        }
+        let compare_path = options.use_http_path || !matches!(query.protocol.as_deref(), Some("http") | Some("https"));
+        check(&query.protocol, &self.protocol)
+            && check(&query.host, &self.host)
+            && (!compare_path || check(&query.path, &self.path))
+            && check(&query.username, &self.username)
+            && (!options.match_password || check(&query.password, &self.password))
+            && (!options.match_password || check(&query.credential, &self.credential))
+    }
+}
+
+#[inline]
+fn check<T: PartialEq>(want: &Option<T>, have: &Option<T>) -> bool {
+    want.as_ref().is_none_or(|want| have.as_ref() == Some(want))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stored() -> GitCredential {
+        GitCredential::builder()
+            .protocol("https")
+            .host("example.com")
+            .path("repo.git")
+            .username("user")
+            .password("secret")
+            .build()
+    }
+
+    #[test]
+    fn missing_fields_are_wildcards() {
+        let options = MatchOptions::new();
+        assert!(stored().matches(&GitCredential::default(), options));
+        assert!(stored().matches(&GitCredential::builder().host("example.com").build(), options));
+        assert!(!stored().matches(&GitCredential::builder().host("example.org").build(), options));
+        assert!(!stored().matches(&GitCredential::builder().protocol("http").build(), options));
+        // A field in the query must be present in the credential.
+        assert!(!GitCredential::default().matches(&GitCredential::builder().host("example.com").build(), options));
+    }
+
+    #[test]
+    fn http_path_is_compared_only_with_use_http_path() {
+        let query = GitCredential::builder()
+            .protocol("https")
+            .host("example.com")
+            .path("other.git")
+            .build();
+        assert!(stored().matches(&query, MatchOptions::new()));
+        assert!(!stored().matches(&query, MatchOptions::new().use_http_path(true)));
+
+        let mut stored = stored();
+        stored.protocol = Some("ssh".to_owned());
+        let mut query = query;
+        query.protocol = Some("ssh".to_owned());
+        assert!(!stored.matches(&query, MatchOptions::new()));
+        query.path = Some("repo.git".to_owned());
+        assert!(stored.matches(&query, MatchOptions::new()));
+    }
+
+    #[test]
+    fn username_must_match() {
+        let query = GitCredential::builder().host("example.com").username("user").build();
+        assert!(stored().matches(&query, MatchOptions::new()));
+        let query = GitCredential::builder().host("example.com").username("other").build();
+        assert!(!stored().matches(&query, MatchOptions::new()));
+    }
+
+    #[test]
+    fn match_password() {
+        let query = GitCredential::builder().host("example.com").password("wrong").build();
+        assert!(stored().matches(&query, MatchOptions::new()));
+        assert!(!stored().matches(&query, MatchOptions::new().match_password(true)));
+        let query = GitCredential::builder().host("example.com").password("secret").build();
+        assert!(stored().matches(&query, MatchOptions::new().match_password(true)));
+
+        let stored = GitCredential::builder().authtype("Bearer").credential("token").build();
+        let query = GitCredential::builder().credential("other").build();
+        assert!(stored.matches(&query, MatchOptions::new()));
+        assert!(!stored.matches(&query, MatchOptions::new().match_password(true)));
+        let query = GitCredential::builder().credential("token").build();
+        assert!(stored.matches(&query, MatchOptions::new().match_password(true)));
+    }
+}
@@ -0,0 +1,182 @@
+// SPDX-FileCopyrightText: 2026 Antoni Szymański
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::GitCredential;
+
+/// Options for [`GitCredential::normalized_with`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NormalizeOptions {
+    normalize_path: bool,
+}
+
+impl NormalizeOptions {
+    pub const fn new() -> Self {
+        Self { normalize_path: false }
+    }
+
+    /// Whether to trim slashes and a trailing `.git` from the path, so that `org/repo.git/` becomes `org/repo`.
+    pub const fn normalize_path(mut self, yes: bool) -> Self {
+        self.normalize_path = yes;
+        self
+    }
+}
+
+impl GitCredential {
+    /// Returns a copy with a canonical `protocol` and `host`. See [`GitCredential::normalized_with`].
+    pub fn normalized(&self) -> Self {
+        self.normalized_with(NormalizeOptions::new())
+    }
+
+    /// Returns a copy with a canonical `protocol` and `host`, so that credentials git would consider different can be
+    /// compared.
+    ///
+    /// The protocol and host are lowercased, and the port is dropped if it is the default one for the protocol. With
+    /// the `url` feature, international domain names are converted to punycode and IP addresses are written in their
+    /// canonical form.
+    pub fn normalized_with(&self, options: NormalizeOptions) -> Self {
+        let mut normalized = self.clone();
+        if let Some(protocol) = &mut normalized.protocol {
+            protocol.make_ascii_lowercase();
+        }
+        if let Some(host) = &self.host {
+            normalized.host = Some(normalize_host(host, normalized.protocol.as_deref()));
+        }
+        if options.normalize_path
+            && let Some(path) = &self.path
+        {
+            let path = path.trim_matches('/');
+            let path = path.strip_suffix(".git").unwrap_or(path).trim_end_matches('/');
+            normalized.path = Some(path).filter(|path| !path.is_empty()).map(str::to_owned);
+        }
+        normalized
+    }
+}
+
+fn normalize_host(host: &str, protocol: Option<&str>) -> String {
+    let (name, port) = split_port(host);
+    let mut host = normalize_name(name);
+    let default_port = protocol.and_then(default_port);
+    if let Some(port) = port.filter(|port| !port.is_empty())
+        && port.parse().ok() != default_port
+    {
+        host.push(':');
+        host.push_str(port);
+    }
+    host
+}
+
+/// Splits `host[:port]`, where the host may be an IPv6 address in brackets. An IPv6 address without brackets has no
+/// port.
+fn split_port(host: &str) -> (&str, Option<&str>) {
+    let end = if host.starts_with('[') {
+        host.find(']').map_or(0, |i| i + 1)
+    } else {
+        0
+    };
+    match host[end..].rfind(':') {
+        Some(i) if (end > 0 || !host[..i].contains(':')) && host[end + i + 1..].bytes().all(|b| b.is_ascii_digit()) => {
+            (&host[..end + i], Some(&host[end + i + 1..]))
+        }
+        _ => (host, None),
+    }
+}
+
+#[cfg(feature = "url")]
+fn normalize_name(name: &str) -> String {
+    match url::Host::parse(name) {
+        Ok(host) => host.to_string(),
+        Err(_) => name.to_ascii_lowercase(),
+    }
+}
+
+#[cfg(not(feature = "url"))]
+fn normalize_name(name: &str) -> String {
+    name.to_ascii_lowercase()
+}
+
+fn default_port(protocol: &str) -> Option<u16> {
+    match protocol {
+        "http" => Some(80),
+        "https" => Some(443),
+        "ssh" => Some(22),
+        "git" => Some(9418),
+        "ftp" => Some(21),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MatchOptions;
+
+    fn host(protocol: &str, host: &str) -> String {
+        let credential = GitCredential::builder().protocol(protocol).host(host).build();
+        credential.normalized().host.unwrap()
+    }
+
+    #[test]
+    fn split_port() {
+        assert_eq!(super::split_port("host:8443"), ("host", Some("8443")));
+        assert_eq!(super::split_port("host:"), ("host", Some("")));
+        assert_eq!(super::split_port("host"), ("host", None));
+        assert_eq!(super::split_port("[::1]:443"), ("[::1]", Some("443")));
+        assert_eq!(super::split_port("[::1]"), ("[::1]", None));
+        assert_eq!(super::split_port("::1"), ("::1", None));
+        assert_eq!(super::split_port("host:port"), ("host:port", None));
+    }
+
+    #[test]
+    fn normalizes_protocol_and_host() {
+        let credential = GitCredential::builder()
+            .protocol("HTTPS")
+            .host("Example.COM:443")
+            .build()
+            .normalized();
+        assert_eq!(credential.protocol.as_deref(), Some("https"));
+        assert_eq!(credential.host.as_deref(), Some("example.com"));
+        assert_eq!(host("https", "host:8443"), "host:8443");
+        assert_eq!(host("http", "host:443"), "host:443");
+        assert_eq!(host("https", "host:"), "host");
+        assert_eq!(host("https", "[::1]:443"), "[::1]");
+        assert_eq!(host("https", "[::1]:8443"), "[::1]:8443");
+        assert_eq!(host("https", "::1"), "::1");
+        assert_eq!(host("unknown", "host:443"), "host:443");
+    }
+
+    #[cfg(feature = "url")]
+    #[test]
+    fn normalizes_international_and_ip_hosts() {
+        assert_eq!(host("https", "Bücher.example"), "xn--bcher-kva.example");
+        assert_eq!(host("https", "[0:0::1]:8443"), "[::1]:8443");
+    }
+
+    #[test]
+    fn normalizes_path() {
+        let credential = GitCredential::builder().path("/org/repo.git/").build();
+        assert_eq!(credential.normalized().path.as_deref(), Some("/org/repo.git/"));
+        let options = NormalizeOptions::new().normalize_path(true);
+        assert_eq!(credential.normalized_with(options).path.as_deref(), Some("org/repo"));
+        let credential = GitCredential::builder().path("/.git").build();
+        assert_eq!(credential.normalized_with(options).path, None);
+    }
+
+    #[test]
+    fn matches_normalized() {
+        let stored = GitCredential::builder()
+            .protocol("https")
+            .host("example.com")
+            .path("org/repo")
+            .username("user")
+            .build();
+        let query = GitCredential::builder()
+            .protocol("HTTPS")
+            .host("Example.com:443")
+            .path("/org/repo.git/")
+            .build();
+        let options = MatchOptions::new().use_http_path(true);
+        assert!(!stored.matches(&query, options));
+        assert!(!stored.matches(&query, options.normalize(NormalizeOptions::new())));
+        assert!(stored.matches(&query, options.normalize(NormalizeOptions::new().normalize_path(true))));
+    }
+}
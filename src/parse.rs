@@ -0,0 +1,324 @@
+// SPDX-FileCopyrightText: 2026 Antoni Szymański
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::{
+    DuplicateKeyCtx, FromReaderError, GitCredential, InvalidLineCtx, InvalidUtf8Ctx, MAX_LINE_LENGTH,
+    MissingTerminatorCtx, ReadLineCtx, TooLongLineCtx, UnknownKeyCtx,
+};
+use snafu::{OptionExt, ResultExt, ensure};
+use std::collections::HashSet;
+use std::io::{self, BufRead, BufReader, Read};
+use std::str;
+use zeroize::Zeroizing;
+
+/// Options controlling how strictly a credential is parsed.
+///
+/// The lenient mode, which [`GitCredential::from_reader`] uses, accepts everything git accepts: the last of several
+/// values for a key wins, unknown keys are kept in [`GitCredential::extra`], and the input may end without a blank
+/// line.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ParseOptions {
+    deny_duplicate_keys: bool,
+    deny_unknown_keys: bool,
+    require_terminator: bool,
+}
+
+impl ParseOptions {
+    pub const fn lenient() -> Self {
+        Self {
+            deny_duplicate_keys: false,
+            deny_unknown_keys: false,
+            require_terminator: false,
+        }
+    }
+
+    /// Rejects duplicate keys, unknown keys and input that ends without a blank line.
+    pub const fn strict() -> Self {
+        Self {
+            deny_duplicate_keys: true,
+            deny_unknown_keys: true,
+            require_terminator: true,
+        }
+    }
+
+    /// Whether a key other than a multi-valued `key[]` may appear only once.
+    pub const fn deny_duplicate_keys(mut self, yes: bool) -> Self {
+        self.deny_duplicate_keys = yes;
+        self
+    }
+
+    /// Whether keys not known to this crate are rejected instead of being kept in [`GitCredential::extra`].
+    pub const fn deny_unknown_keys(mut self, yes: bool) -> Self {
+        self.deny_unknown_keys = yes;
+        self
+    }
+
+    /// Whether the input must end with a blank line.
+    pub const fn require_terminator(mut self, yes: bool) -> Self {
+        self.require_terminator = yes;
+        self
+    }
+
+    /// Reads a credential from `reader`, which may consume bytes after the blank line that ends it.
+    pub fn parse(&self, reader: impl Read) -> Result<GitCredential, FromReaderError> {
+        self.parse_buf(&mut BufReader::new(reader))
+    }
+
+    /// Reads a credential from `reader`, consuming nothing after the blank line that ends it.
+    pub fn parse_buf(&self, reader: &mut impl BufRead) -> Result<GitCredential, FromReaderError> {
+        match self.read_record(reader)? {
+            Some(credential) => Ok(credential),
+            None => Parser::new(*self).finish(false),
+        }
+    }
+
+    /// Returns an iterator over the credentials in `reader`, each ending with a blank line.
+    pub fn records<R: BufRead>(self, reader: R) -> Records<R> {
+        Records {
+            options: self,
+            reader,
+            done: false,
+        }
+    }
+
+    /// Reads the next credential, or returns `None` if `reader` is already at the end of its input.
+    fn read_record(&self, reader: &mut impl BufRead) -> Result<Option<GitCredential>, FromReaderError> {
+        let mut parser = Parser::new(*self);
+        let mut buf = Zeroizing::new(Vec::new());
+        loop {
+            let line_number = parser.next_line_number();
+            if !read_line(reader, &mut buf).context(ReadLineCtx { line_number })? {
+                if parser.line_number == 0 {
+                    return Ok(None);
+                }
+                return parser.finish(false).map(Some);
+            }
+            if !parser.feed(&buf)? {
+                return parser.finish(true).map(Some);
+            }
+        }
+    }
+}
+
+/// An iterator over the credentials in a stream. See [`ParseOptions::records`].
+#[derive(Debug)]
+pub struct Records<R> {
+    options: ParseOptions,
+    reader: R,
+    done: bool,
+}
+
+impl<R: BufRead> Iterator for Records<R> {
+    type Item = Result<GitCredential, FromReaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let record = self.options.read_record(&mut self.reader).transpose();
+        // After an error, the position in the stream is unknown.
+        self.done = !matches!(record, Some(Ok(_)));
+        record
+    }
+}
+
+/// Returns the key of a raw line, if it has one that is valid UTF-8.
+fn key_of(line: &[u8]) -> Option<String> {
+    let end = line.iter().position(|&b| b == b'=')?;
+    str::from_utf8(&line[..end]).ok().map(str::to_owned)
+}
+
+/// Reads the next line into `buf` without its line ending, consuming nothing past it. Returns `false` at the end of
+/// the input.
+fn read_line(reader: &mut impl BufRead, buf: &mut Vec<u8>) -> io::Result<bool> {
+    buf.clear();
+    let n = reader.take(READ_LIMIT).read_until(b'\n', buf)?;
+    trim_line_ending(buf);
+    Ok(n != 0)
+}
+
+/// The most bytes read for a single line: one more than [`MAX_LINE_LENGTH`] plus a CRLF line ending, so that overlong
+/// lines are detected without buffering them.
+pub(crate) const READ_LIMIT: u64 = MAX_LINE_LENGTH as u64 + 3;
+
+/// Removes a trailing `\n` or `\r\n`, like git's `strbuf_getline`. A `\r` without a following `\n` is kept.
+pub(crate) fn trim_line_ending(buf: &mut Vec<u8>) {
+    if buf.last() == Some(&b'\n') {
+        buf.pop();
+        if buf.last() == Some(&b'\r') {
+            buf.pop();
+        }
+    }
+}
+
+/// Parses a credential one line at a time.
+pub(crate) struct Parser {
+    options: ParseOptions,
+    credential: GitCredential,
+    line_number: usize,
+    seen_keys: HashSet<String>,
+}
+
+impl Parser {
+    pub(crate) fn new(options: ParseOptions) -> Self {
+        Self {
+            options,
+            credential: GitCredential::default(),
+            line_number: 0,
+            seen_keys: HashSet::new(),
+        }
+    }
+
+    /// Applies a single line of input, without its line ending. Returns `false` for the blank line that ends the
+    /// credential.
+    pub(crate) fn feed(&mut self, line: &[u8]) -> Result<bool, FromReaderError> {
+        self.line_number += 1;
+        let line_number = self.line_number;
+        if line.is_empty() {
+            return Ok(false);
+        }
+        ensure!(
+            line.len() <= MAX_LINE_LENGTH,
+            TooLongLineCtx {
+                line_number,
+                key: key_of(line)
+            }
+        );
+        let line = str::from_utf8(line).ok().context(InvalidUtf8Ctx {
+            line_number,
+            key: key_of(line),
+        })?;
+        let (key, value) = line.split_once('=').context(InvalidLineCtx { line_number })?;
+        if self.options.deny_duplicate_keys && !key.ends_with("[]") {
+            // `url` discards every attribute read so far, so only the keys after it take effect.
+            if key == "url" {
+                self.seen_keys.clear();
+            }
+            ensure!(
+                self.seen_keys.insert(key.to_owned()),
+                DuplicateKeyCtx { line_number, key }
+            );
+        }
+        if !self.credential.set_attribute(key, value, line_number)? {
+            ensure!(!self.options.deny_unknown_keys, UnknownKeyCtx { line_number, key });
+            self.credential.extra.push((key.to_owned(), value.to_owned()));
+        }
+        Ok(true)
+    }
+
+    /// The number of the line that will be fed next, counting from 1.
+    pub(crate) fn next_line_number(&self) -> usize {
+        self.line_number + 1
+    }
+
+    /// Returns the parsed credential. `terminated` tells whether the input ended with a blank line.
+    pub(crate) fn finish(self, terminated: bool) -> Result<GitCredential, FromReaderError> {
+        ensure!(
+            terminated || !self.options.require_terminator,
+            MissingTerminatorCtx {
+                line_number: self.line_number
+            }
+        );
+        Ok(self.credential)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strict(input: &str) -> Result<GitCredential, FromReaderError> {
+        ParseOptions::strict().parse(input.as_bytes())
+    }
+
+    #[test]
+    fn strict_rejects_duplicate_keys() {
+        let err = strict("protocol=https\nhost=a\nhost=b\n\n").unwrap_err();
+        assert!(matches!(err, FromReaderError::DuplicateKey { line_number: 3, key } if key == "host"));
+        // Multi-valued keys may repeat.
+        let credential = strict("wwwauth[]=Basic\nwwwauth[]=Bearer\n\n").unwrap();
+        assert_eq!(credential.wwwauth, ["Basic", "Bearer"]);
+        // Lenient parsing keeps the last value.
+        let credential = GitCredential::from_reader("host=a\nhost=b\n".as_bytes()).unwrap();
+        assert_eq!(credential.host.as_deref(), Some("b"));
+    }
+
+    #[test]
+    fn strict_allows_keys_again_after_url() {
+        let credential = strict("username=a\nurl=https://h\nusername=b\n\n").unwrap();
+        assert_eq!(credential.username.as_deref(), Some("b"));
+        let err = strict("url=https://h\nusername=a\nusername=b\n\n").unwrap_err();
+        assert!(matches!(err, FromReaderError::DuplicateKey { line_number: 3, .. }));
+    }
+
+    #[test]
+    fn strict_rejects_unknown_keys() {
+        let err = strict("protocol=https\ncustom=x\n\n").unwrap_err();
+        assert!(matches!(err, FromReaderError::UnknownKey { line_number: 2, key } if key == "custom"));
+        let credential = GitCredential::from_reader("custom=x\n".as_bytes()).unwrap();
+        assert_eq!(credential.extra, [("custom".to_owned(), "x".to_owned())]);
+    }
+
+    #[test]
+    fn strict_requires_terminator() {
+        let err = strict("protocol=https\nhost=h\n").unwrap_err();
+        assert!(matches!(err, FromReaderError::MissingTerminator { line_number: 2 }));
+        assert!(GitCredential::from_reader("protocol=https\nhost=h\n".as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn from_buf_reader_leaves_following_bytes() {
+        let mut reader = BufReader::new("host=a\n\nrest\n".as_bytes());
+        let credential = GitCredential::from_buf_reader(&mut reader).unwrap();
+        assert_eq!(credential.host.as_deref(), Some("a"));
+        let mut rest = String::new();
+        reader.read_to_string(&mut rest).unwrap();
+        assert_eq!(rest, "rest\n");
+    }
+
+    #[test]
+    fn records_reads_several_credentials() {
+        let input = "host=a\n\nhost=b\nusername=u\n\nhost=c\n";
+        let hosts: Vec<_> = GitCredential::records(input.as_bytes())
+            .map(|credential| credential.unwrap().host.unwrap())
+            .collect();
+        assert_eq!(hosts, ["a", "b", "c"]);
+
+        let mut records = ParseOptions::strict().records("host=a\n\nhost=b\n".as_bytes());
+        assert!(records.next().unwrap().is_ok());
+        assert!(matches!(
+            records.next().unwrap(),
+            Err(FromReaderError::MissingTerminator { line_number: 1 })
+        ));
+        assert!(records.next().is_none());
+    }
+
+    #[test]
+    fn strips_crlf_but_keeps_lone_carriage_return() {
+        let credential = GitCredential::from_reader("host=a\r\npath=b\r\r\nusername=c\r".as_bytes()).unwrap();
+        assert_eq!(credential.host.as_deref(), Some("a"));
+        assert_eq!(credential.path.as_deref(), Some("b\r"));
+        assert_eq!(credential.username.as_deref(), Some("c\r"));
+        // A CRLF blank line ends the credential.
+        let credential = strict("host=a\r\n\r\n").unwrap();
+        assert_eq!(credential.host.as_deref(), Some("a"));
+    }
+
+    #[test]
+    fn reports_invalid_utf8_with_line_and_key() {
+        let err = GitCredential::from_reader(&b"host=a\npassword=\xff\n"[..]).unwrap_err();
+        assert!(matches!(
+            &err,
+            FromReaderError::InvalidUtf8 { line_number: 2, key: Some(key) } if key == "password"
+        ));
+        assert_eq!(err.to_string(), "Line 2 (key \"password\") is not valid UTF-8");
+        let err = GitCredential::from_reader(&b"\xff=a\n"[..]).unwrap_err();
+        assert!(matches!(
+            err,
+            FromReaderError::InvalidUtf8 {
+                line_number: 1,
+                key: None
+            }
+        ));
+    }
+}
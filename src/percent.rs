@@ -0,0 +1,67 @@
+// SPDX-FileCopyrightText: 2026 Antoni Szymański
+// SPDX-License-Identifier: MPL-2.0
+
+use std::borrow::Cow;
+use std::fmt::Write;
+use zeroize::Zeroize;
+
+/// Percent-encodes every byte of `s` for which `keep` returns false.
+pub(crate) fn encode(s: &str, keep: impl Fn(u8) -> bool) -> Cow<'_, str> {
+    if s.bytes().all(&keep) {
+        return Cow::Borrowed(s);
+    }
+    let mut out = String::with_capacity(s.len() * 3);
+    for b in s.bytes() {
+        if keep(b) {
+            out.push(b as char);
+        } else {
+            let _ = write!(out, "%{b:02X}");
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Decodes `%XX` escapes in `s` the way git does, keeping malformed escapes as they are. Returns `None` if the result
+/// is not valid UTF-8.
+pub(crate) fn decode(s: &str) -> Option<Cow<'_, str>> {
+    if !s.contains('%') {
+        return Some(Cow::Borrowed(s));
+    }
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(b) = bytes.get(i + 1..i + 3).and_then(decode_hex)
+        {
+            out.push(b);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    match String::from_utf8(out) {
+        Ok(out) => Some(Cow::Owned(out)),
+        Err(err) => {
+            err.into_bytes().zeroize();
+            None
+        }
+    }
+}
+
+fn decode_hex(hex: &[u8]) -> Option<u8> {
+    let hi = (hex[0] as char).to_digit(16)?;
+    let lo = (hex[1] as char).to_digit(16)?;
+    Some((hi * 16 + lo) as u8)
+}
+
+/// Unreserved characters as defined by RFC 3986.
+pub(crate) fn is_unreserved(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~')
+}
+
+/// Reserved and unreserved characters as defined by RFC 3986.
+pub(crate) fn is_reserved_or_unreserved(b: u8) -> bool {
+    is_unreserved(b) || b"!*'();:@&=+$,/?#[]".contains(&b)
+}
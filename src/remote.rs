@@ -0,0 +1,166 @@
+// SPDX-FileCopyrightText: 2026 Antoni Szymański
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::credential_url::{NotUrlAddressCtx, check_components};
+use crate::{CredentialUrlError, GitCredential};
+
+impl GitCredential {
+    /// Builds a credential request for a git remote, accepting the syntaxes git's transport layer does:
+    ///
+    /// - URLs such as `https://host/repo.git` or `file:///srv/repo.git`, parsed by [`GitCredential::from_url_str`].
+    /// - `<transport>::<address>`, of which only the address is used. The address must be a URL: other addresses, such
+    ///   as the command of `ext::ssh -i key host`, are only meaningful to the remote helper, so they are rejected with
+    ///   [`CredentialUrlError::NotUrlAddress`] rather than guessed at.
+    /// - scp-like `[user@]host:path`, where the host may be enclosed in brackets, as in `[::1]:repo.git` or
+    ///   `[git@host:2222]:repo.git`. The protocol is `ssh`.
+    /// - Local paths such as `/srv/repo.git` or `../repo`. The protocol is `file`, like for a `file://` URL.
+    ///
+    /// Leading and trailing slashes are trimmed from the path, and an empty path is left unset.
+    pub fn from_remote(remote: &str) -> Result<Self, CredentialUrlError> {
+        let (transport, remote) = split_transport(remote);
+        if is_url(remote) {
+            return Self::from_url_str(remote);
+        }
+        if let Some(transport) = transport {
+            return NotUrlAddressCtx { transport }.fail();
+        }
+        let credential = if is_local(remote) {
+            Self {
+                protocol: Some("file".to_owned()),
+                host: Some(String::new()),
+                path: trim_path(remote),
+                ..Default::default()
+            }
+        } else {
+            from_scp(remote)
+        };
+        check_components(&credential)?;
+        Ok(credential)
+    }
+}
+
+/// Like git's `is_urlschemechar`.
+fn is_scheme_char(first: bool, c: char) -> bool {
+    c.is_ascii_alphanumeric() || (!first && matches!(c, '+' | '-' | '.'))
+}
+
+/// Splits off a `<transport>::` prefix, which selects a remote helper.
+fn split_transport(remote: &str) -> (Option<&str>, &str) {
+    let end = remote
+        .char_indices()
+        .find(|&(i, c)| !is_scheme_char(i == 0, c))
+        .map_or(remote.len(), |(i, _)| i);
+    match remote[end..].strip_prefix("::") {
+        Some(address) if end > 0 => (Some(&remote[..end]), address),
+        _ => (None, remote),
+    }
+}
+
+/// Like git's `is_url`: a scheme followed by `://`.
+fn is_url(remote: &str) -> bool {
+    let Some((scheme, rest)) = remote.split_once(':') else {
+        return false;
+    };
+    !scheme.is_empty() && scheme.char_indices().all(|(i, c)| is_scheme_char(i == 0, c)) && rest.starts_with("//")
+}
+
+/// Like git's `url_is_local_not_ssh`: a path without a colon, or with a slash before the first colon.
+fn is_local(remote: &str) -> bool {
+    let local = match (remote.find(':'), remote.find('/')) {
+        (None, _) => true,
+        (Some(colon), Some(slash)) => slash < colon,
+        (Some(_), None) => false,
+    };
+    local || (cfg!(windows) && has_dos_drive_prefix(remote))
+}
+
+fn has_dos_drive_prefix(remote: &str) -> bool {
+    let bytes = remote.as_bytes();
+    bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':'
+}
+
+/// Parses `[user@]host:path`, like git's `parse_connect_url` and `get_host_and_port`.
+fn from_scp(remote: &str) -> GitCredential {
+    // The host may be enclosed in brackets, either after the user or together with it, so the separating colon is
+    // searched for after the closing bracket.
+    let bracket = remote
+        .find("@[")
+        .map(|i| i + 1)
+        .or_else(|| remote.starts_with('[').then_some(0));
+    let end = bracket
+        .and_then(|start| remote[start..].find(']').map(|i| start + i))
+        .unwrap_or(0);
+    let (host, path) = match remote[end..].find(':') {
+        Some(i) => (&remote[..end + i], Some(&remote[end + i + 1..])),
+        None => (remote, None),
+    };
+    let host = host.replace(['[', ']'], "");
+    let (username, host) = match host.rsplit_once('@') {
+        Some((username, host)) => (Some(username.to_owned()), host),
+        None => (None, host.as_str()),
+    };
+    // Keep IPv6 addresses in brackets, the way they appear in a URL.
+    let host = if host.matches(':').count() > 1 {
+        format!("[{host}]")
+    } else {
+        host.to_owned()
+    };
+    GitCredential {
+        protocol: Some("ssh".to_owned()),
+        host: Some(host),
+        path: path.and_then(trim_path),
+        username,
+        ..Default::default()
+    }
+}
+
+fn trim_path(path: &str) -> Option<String> {
+    Some(path.trim_matches('/'))
+        .filter(|path| !path.is_empty())
+        .map(str::to_owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CredentialUrlError, GitCredential};
+
+    /// The protocol, host, path and username of a request.
+    type Request = (&'static str, &'static str, Option<&'static str>, Option<&'static str>);
+
+    /// Remotes and the request for them.
+    const TABLE: &[(&str, Request)] = &[
+        (
+            "git@host:org/repo.git",
+            ("ssh", "host", Some("org/repo.git"), Some("git")),
+        ),
+        ("[::1]:repo", ("ssh", "[::1]", Some("repo"), None)),
+        ("user@[::1]:repo", ("ssh", "[::1]", Some("repo"), Some("user"))),
+        (
+            "[git@host:2222]:repo.git",
+            ("ssh", "host:2222", Some("repo.git"), Some("git")),
+        ),
+        ("host:", ("ssh", "host", None, None)),
+        ("/srv/repo.git", ("file", "", Some("srv/repo.git"), None)),
+        ("../repo", ("file", "", Some("../repo"), None)),
+        ("file:///srv/repo.git", ("file", "", Some("srv/repo.git"), None)),
+        ("ssh://git@host:2222/p", ("ssh", "host:2222", Some("p"), Some("git"))),
+        ("persistent-https::https://h/p", ("https", "h", Some("p"), None)),
+    ];
+
+    #[test]
+    fn from_remote() {
+        for &(remote, (protocol, host, path, username)) in TABLE {
+            let credential = GitCredential::from_remote(remote).unwrap();
+            assert_eq!(credential.protocol.as_deref(), Some(protocol), "remote: {remote}");
+            assert_eq!(credential.host.as_deref(), Some(host), "remote: {remote}");
+            assert_eq!(credential.path.as_deref(), path, "remote: {remote}");
+            assert_eq!(credential.username.as_deref(), username, "remote: {remote}");
+        }
+    }
+
+    #[test]
+    fn from_remote_rejects_non_url_transport_address() {
+        let err = GitCredential::from_remote("ext::ssh -i key host").unwrap_err();
+        assert!(matches!(err, CredentialUrlError::NotUrlAddress { transport } if transport == "ext"));
+    }
+}
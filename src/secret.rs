@@ -0,0 +1,56 @@
+// SPDX-FileCopyrightText: 2026 Antoni Szymański
+// SPDX-License-Identifier: MPL-2.0
+
+use std::fmt;
+use zeroize::Zeroize;
+
+/// A confidential string, such as a password or a token.
+///
+/// The memory holding it is wiped when it is dropped, and it is printed as `"***"` by `Debug`. Reading the value
+/// requires an explicit call to [`Secret::expose`].
+#[derive(Default, Clone, PartialEq, Eq)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(s: impl Into<String>) -> Self {
+        Self(s.into())
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// Replaces the value with `src`, wiping the old one. The allocation is reused if it is large enough.
+    pub(crate) fn set(&mut self, src: &str) {
+        if self.0.capacity() >= src.len() {
+            self.0.zeroize();
+            self.0.push_str(src);
+        } else {
+            *self = Self::from(src);
+        }
+    }
+}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("\"***\"")
+    }
+}
+
+impl From<String> for Secret {
+    fn from(s: String) -> Self {
+        Self(s)
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(s: &str) -> Self {
+        Self(s.to_owned())
+    }
+}
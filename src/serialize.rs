@@ -0,0 +1,259 @@
+// SPDX-FileCopyrightText: 2026 Antoni Szymański
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::{Capabilities, GitCredential, Secret, format_timestamp, timestamp_from_secs};
+use serde::de::{self, MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+const REDACTED: &str = "***";
+
+/// Serializes a [`GitCredential`] including its secrets. See [`GitCredential::expose_secrets`].
+#[derive(Debug, Clone, Copy)]
+pub struct ExposeSecrets<'a>(&'a GitCredential);
+
+impl GitCredential {
+    /// Returns a view of the credential that serializes `password`, `oauth_refresh_token` and `credential` as they
+    /// are, instead of as `"***"`.
+    pub fn expose_secrets(&self) -> ExposeSecrets<'_> {
+        ExposeSecrets(self)
+    }
+}
+
+/// Uses the same keys as the git-credential format and skips attributes that are not set. Secrets are serialized as
+/// `"***"` unless [`GitCredential::expose_secrets`] is used.
+impl Serialize for GitCredential {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(self, serializer, false)
+    }
+}
+
+impl Serialize for ExposeSecrets<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(self.0, serializer, true)
+    }
+}
+
+fn serialize<S: Serializer>(gc: &GitCredential, serializer: S, expose: bool) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(None)?;
+    serialize_opt(&mut map, "protocol", gc.protocol.as_deref())?;
+    serialize_opt(&mut map, "host", gc.host.as_deref())?;
+    serialize_opt(&mut map, "path", gc.path.as_deref())?;
+    serialize_opt(&mut map, "username", gc.username.as_deref())?;
+    serialize_opt(&mut map, "password", secret(&gc.password, expose))?;
+    if let Some(password_expiry_utc) = gc.password_expiry_utc {
+        map.serialize_entry("password_expiry_utc", &format_timestamp(password_expiry_utc))?;
+    }
+    serialize_opt(&mut map, "oauth_refresh_token", secret(&gc.oauth_refresh_token, expose))?;
+    serialize_opt(&mut map, "authtype", gc.authtype.as_deref())?;
+    serialize_opt(&mut map, "credential", secret(&gc.credential, expose))?;
+    if gc.ephemeral {
+        map.serialize_entry("ephemeral", &true)?;
+    }
+    if !gc.capabilities.is_empty() {
+        map.serialize_entry("capability[]", &gc.capabilities.names().collect::<Vec<_>>())?;
+    }
+    if !gc.wwwauth.is_empty() {
+        map.serialize_entry("wwwauth[]", &gc.wwwauth)?;
+    }
+    if !gc.state.is_empty() {
+        map.serialize_entry("state[]", &gc.state)?;
+    }
+    if gc.quit {
+        map.serialize_entry("quit", &true)?;
+    }
+    for (key, value) in &gc.extra {
+        map.serialize_entry(key, value)?;
+    }
+    map.end()
+}
+
+#[inline]
+fn secret(secret: &Option<Secret>, expose: bool) -> Option<&str> {
+    secret.as_ref().map(|s| if expose { s.expose() } else { REDACTED })
+}
+
+#[inline]
+fn serialize_opt<M: SerializeMap>(map: &mut M, key: &str, value: Option<&str>) -> Result<(), M::Error> {
+    match value {
+        Some(value) => map.serialize_entry(key, value),
+        None => Ok(()),
+    }
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct Repr {
+    protocol: Option<String>,
+    host: Option<String>,
+    path: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    password_expiry_utc: Option<u64>,
+    oauth_refresh_token: Option<String>,
+    authtype: Option<String>,
+    credential: Option<String>,
+    ephemeral: bool,
+    #[serde(rename = "capability[]")]
+    capabilities: Vec<String>,
+    #[serde(rename = "wwwauth[]")]
+    wwwauth: Vec<String>,
+    #[serde(rename = "state[]")]
+    state: Vec<String>,
+    quit: bool,
+    #[serde(flatten)]
+    extra: Extra,
+}
+
+/// Collects the remaining keys in order, which a map type would not preserve.
+#[derive(Default)]
+struct Extra(Vec<(String, String)>);
+
+/// The value of a key not known to this crate. Numbers and booleans are converted to strings, since the
+/// git-credential format has no other types.
+struct ExtraValue(String);
+
+impl<'de> Deserialize<'de> for ExtraValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ExtraValueVisitor;
+
+        impl Visitor<'_> for ExtraValueVisitor {
+            type Value = ExtraValue;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a string, number or boolean")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<ExtraValue, E> {
+                Ok(ExtraValue(v.to_owned()))
+            }
+
+            fn visit_string<E: de::Error>(self, v: String) -> Result<ExtraValue, E> {
+                Ok(ExtraValue(v))
+            }
+
+            fn visit_bool<E: de::Error>(self, v: bool) -> Result<ExtraValue, E> {
+                Ok(ExtraValue(v.to_string()))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<ExtraValue, E> {
+                Ok(ExtraValue(v.to_string()))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<ExtraValue, E> {
+                Ok(ExtraValue(v.to_string()))
+            }
+
+            fn visit_f64<E: de::Error>(self, v: f64) -> Result<ExtraValue, E> {
+                Ok(ExtraValue(v.to_string()))
+            }
+        }
+
+        deserializer.deserialize_any(ExtraValueVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for Extra {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ExtraVisitor;
+
+        impl<'de> Visitor<'de> for ExtraVisitor {
+            type Value = Extra;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a map of string attributes")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Extra, A::Error> {
+                let mut extra = Vec::new();
+                while let Some((key, ExtraValue(value))) = map.next_entry()? {
+                    extra.push((key, value));
+                }
+                Ok(Extra(extra))
+            }
+        }
+
+        deserializer.deserialize_map(ExtraVisitor)
+    }
+}
+
+/// Accepts the keys written by `Serialize`. Unknown capabilities are ignored and unknown keys are kept in `extra`, with
+/// numbers and booleans converted to strings.
+///
+/// A secret equal to the `"***"` placeholder is rejected, since it comes from a credential serialized without
+/// [`GitCredential::expose_secrets`] and would otherwise be used as the actual secret.
+impl<'de> Deserialize<'de> for GitCredential {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = Repr::deserialize(deserializer)?;
+        Ok(Self {
+            protocol: repr.protocol,
+            host: repr.host,
+            path: repr.path,
+            username: repr.username,
+            password: unredact(repr.password, "password")?,
+            password_expiry_utc: repr.password_expiry_utc.and_then(timestamp_from_secs),
+            oauth_refresh_token: unredact(repr.oauth_refresh_token, "oauth_refresh_token")?,
+            authtype: repr.authtype,
+            credential: unredact(repr.credential, "credential")?,
+            ephemeral: repr.ephemeral,
+            capabilities: repr
+                .capabilities
+                .iter()
+                .filter_map(|name| Capabilities::from_name(name))
+                .fold(Capabilities::empty(), Capabilities::union),
+            wwwauth: repr.wwwauth,
+            state: repr.state,
+            quit: repr.quit,
+            extra: repr.extra.0,
+        })
+    }
+}
+
+fn unredact<E: de::Error>(secret: Option<String>, key: &str) -> Result<Option<Secret>, E> {
+    match secret {
+        Some(secret) if secret == REDACTED => Err(E::custom(format_args!(
+            "{key} is the redacted placeholder {REDACTED:?}; serialize with expose_secrets to keep secrets"
+        ))),
+        secret => Ok(secret.map(Secret::from)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GitCredential;
+
+    fn credential() -> GitCredential {
+        GitCredential::builder()
+            .protocol("https")
+            .host("example.com")
+            .username("user")
+            .password("secret")
+            .extra("custom", "value")
+            .build()
+    }
+
+    #[test]
+    fn round_trip_with_exposed_secrets() {
+        let json = serde_json::to_string(&credential().expose_secrets()).unwrap();
+        let credential: GitCredential = serde_json::from_str(&json).unwrap();
+        assert_eq!(credential.password.as_ref().map(|s| s.expose()), Some("secret"));
+        assert_eq!(credential.extra, [("custom".to_owned(), "value".to_owned())]);
+    }
+
+    #[test]
+    fn rejects_redacted_secrets() {
+        let json = serde_json::to_string(&credential()).unwrap();
+        assert!(json.contains(r#""password":"***""#));
+        let err = serde_json::from_str::<GitCredential>(&json).unwrap_err();
+        assert!(err.to_string().contains("redacted placeholder"), "{err}");
+    }
+
+    #[test]
+    fn converts_scalar_extra_values() {
+        let credential: GitCredential = serde_json::from_str(r#"{"n":1,"f":1.5,"b":true,"s":"x"}"#).unwrap();
+        let extra: Vec<_> = credential.extra.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+        assert_eq!(extra, [("n", "1"), ("f", "1.5"), ("b", "true"), ("s", "x")]);
+        assert!(serde_json::from_str::<GitCredential>(r#"{"list":[1]}"#).is_err());
+    }
+}
@@ -0,0 +1,60 @@
+// SPDX-FileCopyrightText: 2026 Antoni Szymański
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::{GitCredential, Secret};
+#[cfg(unix)]
+use std::fs;
+#[cfg(unix)]
+use std::ops::Deref;
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
+
+/// A request for `https://example.com`.
+pub(crate) fn request() -> GitCredential {
+    GitCredential::builder().protocol("https").host("example.com").build()
+}
+
+pub(crate) fn expose(secret: &Option<Secret>) -> Option<&str> {
+    secret.as_ref().map(Secret::expose)
+}
+
+#[cfg(unix)]
+/// A directory that is removed when dropped.
+pub(crate) struct TempDir(PathBuf);
+
+#[cfg(unix)]
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+#[cfg(unix)]
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(unix)]
+/// Returns an empty directory, only accessible by the owner, for the test called `name`.
+pub(crate) fn temp_dir(name: &str) -> TempDir {
+    let dir = std::env::temp_dir().join(format!("gitcredential-{}-{name}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::set_permissions(&dir, fs::Permissions::from_mode(0o700)).unwrap();
+    TempDir(dir)
+}
+
+#[cfg(unix)]
+/// Writes an executable shell script with `body` to `dir/name` and returns its path.
+pub(crate) fn script(dir: &Path, name: &str, body: &str) -> PathBuf {
+    let path = dir.join(name);
+    fs::write(&path, format!("#!/bin/sh\n{body}\n")).unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    path
+}